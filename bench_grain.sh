@@ -0,0 +1,33 @@
+#!/usr/bin/env bash
+set -e
+
+# Poisson grain on a 24 MP frame (lena.png upscaled to 6000x4000 unless an input is given)
+INPUT="${1:-/tmp/bench_grain_input.png}"
+OUTPUT="/tmp/bench_output.jpg"
+BIN="./target/release/imagecli"
+
+if [ ! -f "$BIN" ]; then
+  echo "Building release binary..."
+  cargo build --release
+fi
+
+if [ ! -f "$INPUT" ]; then
+  echo "Generating 6000x4000 input..."
+  $BIN -i lena.png -o "$INPUT" resize --width 6000 --height 4000 --mode exact
+fi
+
+echo "Input: $INPUT"
+echo "Pipeline: grain --model poisson (default size, then --size 0, the densest grain)"
+echo "---"
+
+for size in 25 0; do
+  python3 - "$BIN" "$INPUT" "$OUTPUT" "$size" <<'PY'
+import resource, subprocess, sys, time
+bin, input, output, size = sys.argv[1:]
+start = time.time()
+subprocess.run([bin, "-i", input, "-o", output, "grain", "--model", "poisson", "--size", size], check=True)
+elapsed = (time.time() - start) * 1000
+peak = resource.getrusage(resource.RUSAGE_CHILDREN).ru_maxrss // 1024
+print(f"--size {size}: {elapsed:.0f} ms, peak memory {peak} MB")
+PY
+done
@@ -0,0 +1,296 @@
+use std::path::Path;
+
+use image::{DynamicImage, Rgb, RgbImage};
+use serde::Serialize;
+
+use super::hsl::rgb_to_hsl;
+use super::white_balance::correlated_temperature;
+use crate::utils::{luminance, srgb_to_linear};
+
+/// Longest side of the copy used to find dominant colors.
+const PALETTE_SIZE: u32 = 128;
+
+#[derive(Serialize)]
+pub struct Percentiles {
+    pub p1: u8,
+    pub p5: u8,
+    pub p25: u8,
+    pub p75: u8,
+    pub p95: u8,
+    pub p99: u8,
+}
+
+/// Statistics of one channel, in 8-bit code values.
+#[derive(Serialize)]
+pub struct ChannelStats {
+    pub mean: f64,
+    pub median: u8,
+    pub std_dev: f64,
+    pub min: u8,
+    pub max: u8,
+    pub percentiles: Percentiles,
+    pub histogram: Vec<u64>,
+}
+
+#[derive(Serialize)]
+pub struct Channels {
+    pub red: ChannelStats,
+    pub green: ChannelStats,
+    pub blue: ChannelStats,
+    pub luminance: ChannelStats,
+}
+
+/// Percentage of pixels with at least one channel at 0 (shadows) or 255 (highlights).
+#[derive(Serialize)]
+pub struct Clipping {
+    pub shadows: f64,
+    pub highlights: f64,
+}
+
+#[derive(Serialize)]
+pub struct DominantColor {
+    pub hex: String,
+    pub rgb: [u8; 3],
+    /// Fraction of the image (0–1) closest to this color
+    pub share: f64,
+}
+
+#[derive(Serialize)]
+pub struct Analysis {
+    pub width: u32,
+    pub height: u32,
+    pub channels: Channels,
+    pub clipping: Clipping,
+    /// Mean HSL saturation (0–100)
+    pub saturation: f64,
+    pub dominant_colors: Vec<DominantColor>,
+    /// Correlated color temperature of the gray-world average, in kelvin
+    pub color_temperature: Option<u32>,
+}
+
+fn channel_stats(histogram: [u64; 256]) -> ChannelStats {
+    let total: u64 = histogram.iter().sum();
+    let mean = histogram.iter().enumerate().map(|(v, &n)| v as f64 * n as f64).sum::<f64>() / total as f64;
+    let variance = histogram
+        .iter()
+        .enumerate()
+        .map(|(v, &n)| (v as f64 - mean).powi(2) * n as f64)
+        .sum::<f64>()
+        / total as f64;
+
+    // Smallest value with at least `p` percent of the pixels at or below it
+    let percentile = |p: f64| -> u8 {
+        let target = (total as f64 * p / 100.0).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (v, &n) in histogram.iter().enumerate() {
+            seen += n;
+            if seen >= target {
+                return v as u8;
+            }
+        }
+        255
+    };
+
+    ChannelStats {
+        mean: round2(mean),
+        median: percentile(50.0),
+        std_dev: round2(variance.sqrt()),
+        min: histogram.iter().position(|&n| n > 0).unwrap_or(0) as u8,
+        max: histogram.iter().rposition(|&n| n > 0).unwrap_or(0) as u8,
+        percentiles: Percentiles {
+            p1: percentile(1.0),
+            p5: percentile(5.0),
+            p25: percentile(25.0),
+            p75: percentile(75.0),
+            p95: percentile(95.0),
+            p99: percentile(99.0),
+        },
+        histogram: histogram.to_vec(),
+    }
+}
+
+fn round2(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
+
+/// Median-cut palette: repeatedly split the box with the widest channel range
+/// at its median until there are `count` boxes, then refine the box averages
+/// with a few k-means passes so shares reflect the nearest-color areas.
+fn dominant_colors(img: &DynamicImage, count: usize) -> Vec<DominantColor> {
+    let small = img.thumbnail(PALETTE_SIZE, PALETTE_SIZE).to_rgb8();
+    let pixels: Vec<[f64; 3]> = small.pixels().map(|p| p.0.map(|v| v as f64)).collect();
+    let mut boxes: Vec<Vec<[f64; 3]>> = vec![pixels.clone()];
+
+    let widest = |pixels: &[[f64; 3]]| -> (usize, f64) {
+        (0..3)
+            .map(|c| {
+                let (lo, hi) = pixels.iter().fold((255.0f64, 0.0f64), |(lo, hi), p| (p[c].min(lo), p[c].max(hi)));
+                (c, hi - lo)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((0, 0.0))
+    };
+    while boxes.len() < count {
+        let Some((i, (channel, _))) = boxes
+            .iter()
+            .enumerate()
+            .map(|(i, b)| (i, widest(b)))
+            .filter(|(_, (_, range))| *range > 0.0)
+            .max_by(|(i, (_, a)), (j, (_, b))| (a * boxes[*i].len() as f64).total_cmp(&(b * boxes[*j].len() as f64)))
+        else {
+            break;
+        };
+        let mut split = boxes.swap_remove(i);
+        split.sort_by(|a, b| a[channel].total_cmp(&b[channel]));
+        let upper = split.split_off(split.len() / 2);
+        boxes.push(split);
+        boxes.push(upper);
+    }
+
+    let mean = |members: &[[f64; 3]]| -> [f64; 3] {
+        let n = members.len().max(1) as f64;
+        [0, 1, 2].map(|c| members.iter().map(|p| p[c]).sum::<f64>() / n)
+    };
+    let mut centers: Vec<[f64; 3]> = boxes.iter().map(|b| mean(b)).collect();
+    let mut counts = vec![0usize; centers.len()];
+    for _ in 0..5 {
+        let mut members: Vec<Vec<[f64; 3]>> = vec![Vec::new(); centers.len()];
+        for p in &pixels {
+            let distance = |c: &[f64; 3]| (0..3).map(|i| (p[i] - c[i]).powi(2)).sum::<f64>();
+            let nearest = (0..centers.len())
+                .min_by(|&a, &b| distance(&centers[a]).total_cmp(&distance(&centers[b])))
+                .unwrap_or(0);
+            members[nearest].push(*p);
+        }
+        for (center, group) in centers.iter_mut().zip(&members) {
+            if !group.is_empty() {
+                *center = mean(group);
+            }
+        }
+        counts = members.iter().map(Vec::len).collect();
+    }
+
+    let total = pixels.len().max(1) as f64;
+    let mut colors: Vec<DominantColor> = centers
+        .iter()
+        .zip(counts)
+        .filter(|(_, n)| *n > 0)
+        .map(|(center, n)| {
+            let rgb = center.map(|v| v.round() as u8);
+            DominantColor {
+                hex: format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2]),
+                rgb,
+                share: (n as f64 / total * 1000.0).round() / 1000.0,
+            }
+        })
+        .collect();
+    colors.sort_by(|a, b| b.share.total_cmp(&a.share));
+    colors
+}
+
+/// Histogram plot in the `show-curve` style: red, green and blue histograms
+/// drawn additively (overlaps turn yellow, cyan, magenta and white), with
+/// luminance as a gray outline.
+fn render_histogram(channels: &Channels) -> DynamicImage {
+    let size: u32 = 256;
+    let mut img = RgbImage::from_pixel(size, size, Rgb([30, 30, 30]));
+
+    // Draw grid lines at 25%, 50%, 75%
+    for pct in [0.25, 0.5, 0.75] {
+        let p = (pct * size as f64) as u32;
+        for i in 0..size {
+            img.put_pixel(p, i, Rgb([60, 60, 60]));
+            img.put_pixel(i, p, Rgb([60, 60, 60]));
+        }
+    }
+
+    // Scale to the tallest bin, ignoring the end bins so clipping spikes
+    // don't flatten the rest
+    let rgb = [&channels.red, &channels.green, &channels.blue];
+    let peak = rgb
+        .iter()
+        .chain([&&channels.luminance])
+        .flat_map(|c| c.histogram[1..255].iter())
+        .copied()
+        .max()
+        .unwrap_or(1)
+        .max(1) as f64;
+    let bar = |n: u64| ((n as f64 / peak).min(1.0) * (size - 1) as f64).round() as u32;
+
+    for x in 0..size {
+        let heights = rgb.map(|c| bar(c.histogram[x as usize]));
+        for y in 0..size {
+            let from_bottom = size - 1 - y;
+            let mut pixel = *img.get_pixel(x, y);
+            let mut covered = false;
+            for (c, &height) in heights.iter().enumerate() {
+                if from_bottom < height {
+                    pixel[c] = 200;
+                    covered = true;
+                }
+            }
+            if covered {
+                img.put_pixel(x, y, pixel);
+            }
+        }
+    }
+
+    // Luminance outline, joined between neighbouring bins
+    let tops: Vec<u32> = (0..size).map(|x| size - 1 - bar(channels.luminance.histogram[x as usize]).min(size - 1)).collect();
+    for x in 0..size {
+        let prev = tops[x.saturating_sub(1) as usize];
+        let (lo, hi) = (prev.min(tops[x as usize]), prev.max(tops[x as usize]));
+        for y in lo..=hi {
+            img.put_pixel(x, y, Rgb([235, 235, 235]));
+        }
+    }
+
+    DynamicImage::ImageRgb8(img)
+}
+
+/// Compute histograms, percentiles, clipping, saturation, `colors` dominant
+/// colors and color temperature. With `render`, also save a histogram plot.
+pub fn apply(img: &DynamicImage, colors: usize, render: Option<&Path>) -> Analysis {
+    let rgb = img.to_rgb8();
+    let total = (rgb.width() as u64 * rgb.height() as u64).max(1);
+
+    let mut histograms = [[0u64; 256]; 4];
+    let (mut shadows, mut highlights) = (0u64, 0u64);
+    let mut saturation = 0.0;
+    let mut linear_sum = [0.0; 3];
+    for p in rgb.pixels() {
+        for c in 0..3 {
+            histograms[c][p[c] as usize] += 1;
+            linear_sum[c] += srgb_to_linear(p[c] as f64 / 255.0);
+        }
+        let (r, g, b) = (p[0] as f64, p[1] as f64, p[2] as f64);
+        histograms[3][luminance(r, g, b).round() as usize] += 1;
+        shadows += p.0.contains(&0) as u64;
+        highlights += p.0.contains(&255) as u64;
+        saturation += rgb_to_hsl(r / 255.0, g / 255.0, b / 255.0).1;
+    }
+
+    let [red, green, blue, luma] = histograms;
+    let channels = Channels {
+        red: channel_stats(red),
+        green: channel_stats(green),
+        blue: channel_stats(blue),
+        luminance: channel_stats(luma),
+    };
+    if let Some(path) = render {
+        render_histogram(&channels)
+            .save(path)
+            .unwrap_or_else(|e| panic!("failed to save {}: {e}", path.display()));
+    }
+
+    let percent = |n: u64| round2(n as f64 / total as f64 * 100.0);
+    Analysis {
+        width: rgb.width(),
+        height: rgb.height(),
+        channels,
+        clipping: Clipping { shadows: percent(shadows), highlights: percent(highlights) },
+        saturation: round2(saturation / total as f64 * 100.0),
+        dominant_colors: dominant_colors(img, colors),
+        color_temperature: correlated_temperature(linear_sum).map(|k| k.round() as u32),
+    }
+}
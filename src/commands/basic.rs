@@ -0,0 +1,74 @@
+use image::DynamicImage;
+
+use crate::utils::{keep_alpha, linear_to_srgb, smoothstep, srgb_to_linear, tone_push};
+
+/// Build a 256-entry LUT for the basic-panel tone controls.
+/// `exposure` is in EV stops; the other controls are on a -1 to 1 scale.
+fn build_basic_lut(
+    exposure: f64,
+    contrast: f64,
+    highlights: f64,
+    shadows: f64,
+    whites: f64,
+    blacks: f64,
+) -> [u8; 256] {
+    let gain = 2f64.powf(exposure);
+
+    // S-curve exponent around the 0.5 pivot: > 1 adds contrast, < 1 flattens
+    let k = 2f64.powf(contrast * 1.5);
+
+    // Clipping points: positive whites/blacks brighten, negative darken
+    let white_point = 1.0 - whites * 0.25;
+    let black_point = -blacks * 0.25;
+
+    let mut lut = [0u8; 256];
+    for (i, out) in lut.iter_mut().enumerate() {
+        let v = i as f64 / 255.0;
+
+        // Exposure in linear light
+        let v = linear_to_srgb((srgb_to_linear(v) * gain).min(1.0));
+
+        // Contrast around middle gray
+        let v = if v < 0.5 {
+            0.5 * (2.0 * v).powf(k)
+        } else {
+            1.0 - 0.5 * (2.0 * (1.0 - v)).powf(k)
+        };
+
+        // Highlights / shadows: tonal-range pushes weighted like color-grade
+        let v = tone_push(v, highlights, smoothstep(0.5, 1.0, v));
+        let v = tone_push(v, shadows, 1.0 - smoothstep(0.0, 0.5, v));
+
+        // Whites / blacks: remap the clipping points
+        let v = (v - black_point) / (white_point - black_point);
+
+        *out = (v * 255.0).round().clamp(0.0, 255.0) as u8;
+    }
+    lut
+}
+
+pub fn apply(
+    img: DynamicImage,
+    exposure: f32,
+    contrast: i32,
+    highlights: i32,
+    shadows: i32,
+    whites: i32,
+    blacks: i32,
+) -> DynamicImage {
+    let exposure = exposure.clamp(-5.0, 5.0) as f64;
+    let contrast = contrast.clamp(-100, 100) as f64 / 100.0;
+    let highlights = highlights.clamp(-100, 100) as f64 / 100.0;
+    let shadows = shadows.clamp(-100, 100) as f64 / 100.0;
+    let whites = whites.clamp(-100, 100) as f64 / 100.0;
+    let blacks = blacks.clamp(-100, 100) as f64 / 100.0;
+
+    let lut = build_basic_lut(exposure, contrast, highlights, shadows, whites, blacks);
+    let mut rgb = img.to_rgb8();
+    for pixel in rgb.pixels_mut() {
+        pixel[0] = lut[pixel[0] as usize];
+        pixel[1] = lut[pixel[1] as usize];
+        pixel[2] = lut[pixel[2] as usize];
+    }
+    keep_alpha(&img, rgb)
+}
@@ -0,0 +1,20 @@
+use image::DynamicImage;
+
+/// Mix RGB through a 3x3 matrix (rows are output channels, values in percent)
+/// plus a constant offset per output channel (percent of full scale).
+pub fn apply(img: DynamicImage, red: [i32; 3], green: [i32; 3], blue: [i32; 3], constant: [i32; 3]) -> DynamicImage {
+    let row = |v: [i32; 3]| -> [f64; 3] { v.map(|v| v.clamp(-200, 200) as f64 / 100.0) };
+    let matrix = [row(red), row(green), row(blue)];
+    let constant = row(constant).map(|c| c.clamp(-1.0, 1.0) * 255.0);
+
+    let mut rgb = img.to_rgb8();
+    for pixel in rgb.pixels_mut() {
+        let src = [pixel[0] as f64, pixel[1] as f64, pixel[2] as f64];
+        for c in 0..3 {
+            let m = matrix[c];
+            let v = m[0] * src[0] + m[1] * src[1] + m[2] * src[2] + constant[c];
+            pixel[c] = v.round().clamp(0.0, 255.0) as u8;
+        }
+    }
+    DynamicImage::ImageRgb8(rgb)
+}
@@ -1,5 +1,7 @@
 use image::DynamicImage;
 
+use crate::utils::luminance;
+
 pub fn apply(img: DynamicImage, temperature: i32, tint: i32, vibrance: i32, saturation: i32) -> DynamicImage {
     let temperature = temperature.clamp(-100, 100) as f64;
     let tint = tint.clamp(-100, 100) as f64;
@@ -18,8 +20,7 @@ pub fn apply(img: DynamicImage, temperature: i32, tint: i32, vibrance: i32, satu
         let g = (pixel[1] as f64 * g_scale).clamp(0.0, 255.0);
         let b = (pixel[2] as f64 * b_scale).clamp(0.0, 255.0);
 
-        // Luminance (Rec. 709)
-        let lum = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let lum = luminance(r, g, b);
 
         // Pixel saturation for vibrance weighting
         let max_ch = r.max(g).max(b);
@@ -1,8 +1,8 @@
 use image::DynamicImage;
 
-use crate::utils::smoothstep;
+use crate::utils::{luminance, smoothstep};
 
-fn hue_to_rgb(hue: f64) -> (f64, f64, f64) {
+pub(crate) fn hue_to_rgb(hue: f64) -> (f64, f64, f64) {
     let h = (hue % 360.0) / 60.0;
     let x = 1.0 - ((h % 2.0) - 1.0).abs();
     match h as u32 {
@@ -15,6 +15,7 @@ fn hue_to_rgb(hue: f64) -> (f64, f64, f64) {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn apply(
     img: DynamicImage,
     shadows_hue: u32, shadows_sat: u32, shadows_lum: i32,
@@ -42,7 +43,7 @@ pub fn apply(
         let r = pixel[0] as f64;
         let g = pixel[1] as f64;
         let b = pixel[2] as f64;
-        let lum = (0.2126 * r + 0.7152 * g + 0.0722 * b) / 255.0;
+        let lum = luminance(r, g, b) / 255.0;
 
         let shadows_w = 1.0 - smoothstep(0.0, 0.5, lum);
         let highlights_w = smoothstep(0.5, 1.0, lum);
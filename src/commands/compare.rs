@@ -0,0 +1,268 @@
+use std::fmt;
+
+use clap::ValueEnum;
+use image::imageops::FilterType;
+use image::{DynamicImage, Rgb, RgbImage, Rgba, RgbaImage};
+
+use super::watermark::render_text;
+use super::white_balance::linear_to_xyz;
+use crate::utils::{box_mean, luminance, relative_sigma, srgb_to_linear};
+
+/// How to present the two images.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CompareMode {
+    /// One image, showing the input on one side of a wipe and the other image on the other
+    Split,
+    /// Both images next to each other with labels
+    SideBySide,
+    /// Heatmap of the per-pixel color difference (ΔE 2000)
+    Difference,
+}
+
+/// Direction of the wipe in split mode.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum WipeDirection {
+    /// Input on the left, other on the right
+    Vertical,
+    /// Input on top, other below
+    Horizontal,
+    /// Input top-left, other bottom-right
+    Diagonal,
+}
+
+/// Similarity of two same-sized images.
+pub struct Metrics {
+    /// Peak signal-to-noise ratio over RGB, in dB (infinite when identical)
+    pub psnr: f64,
+    /// Mean structural similarity of the luminance (1 when identical)
+    pub ssim: f64,
+    pub mean_delta_e: f64,
+    pub max_delta_e: f64,
+}
+
+impl fmt::Display for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let psnr = if self.psnr.is_finite() { format!("{:.2} dB", self.psnr) } else { "inf".into() };
+        write!(
+            f,
+            "PSNR {psnr}, SSIM {:.4}, mean ΔE {:.2}, max ΔE {:.2}",
+            self.ssim, self.mean_delta_e, self.max_delta_e
+        )
+    }
+}
+
+/// CIE L*a*b* (D65) of an 8-bit sRGB pixel.
+fn srgb_to_lab(p: &Rgb<u8>) -> [f64; 3] {
+    let xyz = linear_to_xyz(p.0.map(|v| srgb_to_linear(v as f64 / 255.0)));
+    let white = [0.95047, 1.0, 1.08883];
+    let f = |t: f64| if t > 216.0 / 24389.0 { t.cbrt() } else { (24389.0 / 27.0 * t + 16.0) / 116.0 };
+    let [fx, fy, fz] = [0, 1, 2].map(|c| f(xyz[c] / white[c]));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// CIEDE2000 color difference.
+fn delta_e_2000(lab1: [f64; 3], lab2: [f64; 3]) -> f64 {
+    let [l1, a1, b1] = lab1;
+    let [l2, a2, b2] = lab2;
+    let c_mean = ((a1.hypot(b1)) + (a2.hypot(b2))) / 2.0;
+    let g = 0.5 * (1.0 - (c_mean.powi(7) / (c_mean.powi(7) + 25f64.powi(7))).sqrt());
+    let (a1, a2) = (a1 * (1.0 + g), a2 * (1.0 + g));
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let hue = |b: f64, a: f64| if a == 0.0 && b == 0.0 { 0.0 } else { b.atan2(a).to_degrees().rem_euclid(360.0) };
+    let (h1, h2) = (hue(b1, a1), hue(b2, a2));
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 <= h1 {
+        h2 - h1 + 360.0
+    } else {
+        h2 - h1 - 360.0
+    };
+    let dh_big = 2.0 * (c1 * c2).sqrt() * (dh.to_radians() / 2.0).sin();
+
+    let l_mean = (l1 + l2) / 2.0;
+    let c_mean = (c1 + c2) / 2.0;
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+    let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_mean).to_radians().cos()
+        + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+    let sl = 1.0 + 0.015 * (l_mean - 50.0).powi(2) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+    let sc = 1.0 + 0.045 * c_mean;
+    let sh = 1.0 + 0.015 * c_mean * t;
+    let rt = -2.0
+        * (c_mean.powi(7) / (c_mean.powi(7) + 25f64.powi(7))).sqrt()
+        * (60.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp()).to_radians().sin();
+
+    let (l, c, h) = (dl / sl, dc / sc, dh_big / sh);
+    (l * l + c * c + h * h + rt * c * h).max(0.0).sqrt()
+}
+
+/// Per-pixel ΔE 2000 between two same-sized images.
+fn delta_e_map(a: &RgbImage, b: &RgbImage) -> Vec<f64> {
+    a.pixels().zip(b.pixels()).map(|(pa, pb)| delta_e_2000(srgb_to_lab(pa), srgb_to_lab(pb))).collect()
+}
+
+/// Mean SSIM of the luminance over 7x7 windows.
+fn ssim(a: &RgbImage, b: &RgbImage) -> f64 {
+    let (w, h) = (a.width() as usize, a.height() as usize);
+    let lum = |img: &RgbImage| -> Vec<f64> {
+        img.pixels().map(|p| luminance(p[0] as f64, p[1] as f64, p[2] as f64)).collect()
+    };
+    let (x, y) = (lum(a), lum(b));
+    let product = |p: &[f64], q: &[f64]| -> Vec<f64> { p.iter().zip(q).map(|(u, v)| u * v).collect() };
+    let r = 3;
+    let (mu_x, mu_y) = (box_mean(&x, w, h, r), box_mean(&y, w, h, r));
+    let (xx, yy, xy) = (
+        box_mean(&product(&x, &x), w, h, r),
+        box_mean(&product(&y, &y), w, h, r),
+        box_mean(&product(&x, &y), w, h, r),
+    );
+
+    let (c1, c2) = ((0.01 * 255.0f64).powi(2), (0.03 * 255.0f64).powi(2));
+    let sum: f64 = (0..w * h)
+        .map(|i| {
+            let (var_x, var_y) = (xx[i] - mu_x[i] * mu_x[i], yy[i] - mu_y[i] * mu_y[i]);
+            let cov = xy[i] - mu_x[i] * mu_y[i];
+            ((2.0 * mu_x[i] * mu_y[i] + c1) * (2.0 * cov + c2))
+                / ((mu_x[i] * mu_x[i] + mu_y[i] * mu_y[i] + c1) * (var_x + var_y + c2))
+        })
+        .sum();
+    sum / (w * h) as f64
+}
+
+/// PSNR, SSIM and ΔE 2000 statistics of `other` against `img` (same size).
+pub fn metrics(img: &DynamicImage, other: &DynamicImage) -> Metrics {
+    let (a, b) = (img.to_rgb8(), other.to_rgb8());
+    let squared: f64 = a
+        .as_raw()
+        .iter()
+        .zip(b.as_raw())
+        .map(|(&u, &v)| (u as f64 - v as f64).powi(2))
+        .sum();
+    let mse = squared / a.as_raw().len() as f64;
+    let delta_e = delta_e_map(&a, &b);
+    Metrics {
+        psnr: 10.0 * (255.0 * 255.0 / mse).log10(),
+        ssim: ssim(&a, &b),
+        mean_delta_e: delta_e.iter().sum::<f64>() / delta_e.len() as f64,
+        max_delta_e: delta_e.iter().copied().fold(0.0, f64::max),
+    }
+}
+
+/// Resize `other` to the size of `img` when they differ, so every view and
+/// metric compares matching pixels.
+pub fn match_size(img: &DynamicImage, other: DynamicImage) -> DynamicImage {
+    if other.width() == img.width() && other.height() == img.height() {
+        return other;
+    }
+    eprintln!(
+        "compare: resizing {}x{} to {}x{} to match the input",
+        other.width(),
+        other.height(),
+        img.width(),
+        img.height()
+    );
+    other.resize_exact(img.width(), img.height(), FilterType::Lanczos3)
+}
+
+/// Wipe between the two images at `position` (0–100) with a thin white divider.
+fn split(img: &DynamicImage, other: &DynamicImage, direction: WipeDirection, position: f64) -> DynamicImage {
+    let (a, b) = (img.to_rgb8(), other.to_rgb8());
+    let (w, h) = (a.width() as f64, a.height() as f64);
+    let t = position.clamp(0.0, 100.0) / 100.0;
+    let half_line = relative_sigma(a.width(), a.height(), 3.0, 1.0) as f64 / 2.0;
+
+    let out = RgbImage::from_fn(a.width(), a.height(), |x, y| {
+        let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+        // Signed distance in pixels from the wipe line (negative on the input side)
+        let distance = match direction {
+            WipeDirection::Vertical => px - t * w,
+            WipeDirection::Horizontal => py - t * h,
+            WipeDirection::Diagonal => (px / w + py / h - 2.0 * t) / (1.0 / (w * w) + 1.0 / (h * h)).sqrt(),
+        };
+        if distance.abs() < half_line {
+            Rgb([255, 255, 255])
+        } else if distance < 0.0 {
+            *a.get_pixel(x, y)
+        } else {
+            *b.get_pixel(x, y)
+        }
+    });
+    DynamicImage::ImageRgb8(out)
+}
+
+/// Both images in a row on a dark background, each with a label above it.
+fn side_by_side(img: &DynamicImage, other: &DynamicImage, labels: &[String]) -> DynamicImage {
+    let (w, h) = (img.width(), img.height());
+    let gap = relative_sigma(w, h, 24.0, 4.0).round() as u32;
+    let text_height = relative_sigma(w, h, 40.0, 10.0);
+    let strip = (text_height * 1.6).round() as u32;
+
+    let mut canvas = RgbaImage::from_pixel(2 * w + 3 * gap, h + strip + 2 * gap, Rgba([30, 30, 30, 255]));
+    for (i, panel) in [img, other].into_iter().enumerate() {
+        let x = gap + i as u32 * (w + gap);
+        image::imageops::replace(&mut canvas, &panel.to_rgba8(), x as i64, (gap + strip) as i64);
+
+        if let Some(label) = labels.get(i).filter(|l| !l.is_empty()) {
+            let text = render_text(label, None, Rgba([220, 220, 220, 255]), text_height);
+            let tx = x as i64 + (w as i64 - text.width() as i64) / 2;
+            let ty = gap as i64 + (strip as i64 - text.height() as i64) / 2;
+            image::imageops::overlay(&mut canvas, &text, tx, ty);
+        }
+    }
+    DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(canvas).to_rgb8())
+}
+
+/// Heatmap color for a 0–1 value: black → purple → red → yellow → white.
+fn heat(t: f64) -> [f64; 3] {
+    const STOPS: [[f64; 3]; 5] = [[0.0, 0.0, 0.0], [90.0, 0.0, 130.0], [220.0, 40.0, 40.0], [250.0, 190.0, 0.0], [255.0, 255.0, 255.0]];
+    let pos = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+    let i = (pos.floor() as usize).min(STOPS.len() - 2);
+    let f = pos - i as f64;
+    [0, 1, 2].map(|c| STOPS[i][c] + (STOPS[i + 1][c] - STOPS[i][c]) * f)
+}
+
+/// ΔE 2000 per pixel, multiplied by `amplify` (so ΔE 100/`amplify` is white),
+/// over a dimmed grayscale copy of the input for orientation.
+fn difference(img: &DynamicImage, other: &DynamicImage, amplify: f64) -> DynamicImage {
+    let (a, b) = (img.to_rgb8(), other.to_rgb8());
+    let delta_e = delta_e_map(&a, &b);
+    let mut out = RgbImage::new(a.width(), a.height());
+    for ((pixel, src), de) in out.pixels_mut().zip(a.pixels()).zip(delta_e) {
+        let dim = luminance(src[0] as f64, src[1] as f64, src[2] as f64) * 0.25;
+        let color = heat(de * amplify / 100.0);
+        *pixel = Rgb(color.map(|c| c.max(dim).round() as u8));
+    }
+    DynamicImage::ImageRgb8(out)
+}
+
+/// Render a comparison of `img` (before) and `other` (after), which must be
+/// the same size (see `match_size`).
+pub fn apply(
+    img: &DynamicImage,
+    other: &DynamicImage,
+    mode: CompareMode,
+    direction: WipeDirection,
+    position: f64,
+    labels: &[String],
+    amplify: f64,
+) -> DynamicImage {
+    match mode {
+        CompareMode::Split => split(img, other, direction, position),
+        CompareMode::SideBySide => side_by_side(img, other, labels),
+        CompareMode::Difference => difference(img, other, amplify),
+    }
+}
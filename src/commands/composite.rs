@@ -0,0 +1,150 @@
+use clap::ValueEnum;
+use image::imageops::FilterType;
+use image::{DynamicImage, RgbaImage};
+
+use crate::utils::{Gravity, Length};
+
+/// How the layer's color combines with the base image.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum BlendMode {
+    /// Layer replaces the base
+    Normal,
+    /// Darkens: white is neutral (textures, shadows)
+    Multiply,
+    /// Lightens: black is neutral (light leaks, glows)
+    Screen,
+    /// Multiply in the shadows, screen in the highlights (contrasty textures)
+    Overlay,
+    /// Gentle overlay (paper and film-scan textures)
+    SoftLight,
+    /// Layer hue and saturation with base luminosity
+    Color,
+    /// Layer luminosity with base hue and saturation
+    Luminosity,
+    /// Absolute difference (alignment checks, graphic effects)
+    Difference,
+}
+
+/// Luminosity as defined for the non-separable blend modes.
+fn lum(c: [f32; 3]) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+/// Give `c` the luminosity `l`, clipping back into gamut around it.
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    let c = c.map(|v| v + d);
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    c.map(|v| {
+        let mut v = v;
+        if n < 0.0 {
+            v = l + (v - l) * l / (l - n);
+        }
+        if x > 1.0 {
+            v = l + (v - l) * (1.0 - l) / (x - l);
+        }
+        v
+    })
+}
+
+fn blend(mode: BlendMode, b: [f32; 3], s: [f32; 3]) -> [f32; 3] {
+    let separable = |f: fn(f32, f32) -> f32| [f(b[0], s[0]), f(b[1], s[1]), f(b[2], s[2])];
+    match mode {
+        BlendMode::Normal => s,
+        BlendMode::Multiply => separable(|b, s| b * s),
+        BlendMode::Screen => separable(|b, s| b + s - b * s),
+        BlendMode::Overlay => separable(|b, s| {
+            if b <= 0.5 {
+                2.0 * b * s
+            } else {
+                1.0 - 2.0 * (1.0 - b) * (1.0 - s)
+            }
+        }),
+        BlendMode::SoftLight => separable(|b, s| {
+            if s <= 0.5 {
+                b - (1.0 - 2.0 * s) * b * (1.0 - b)
+            } else {
+                let d = if b <= 0.25 { ((16.0 * b - 12.0) * b + 4.0) * b } else { b.sqrt() };
+                b + (2.0 * s - 1.0) * (d - b)
+            }
+        }),
+        BlendMode::Color => set_lum(s, lum(b)),
+        BlendMode::Luminosity => set_lum(b, lum(s)),
+        BlendMode::Difference => separable(|b, s| (b - s).abs()),
+    }
+}
+
+/// Composite `layer` over `img`. The layer is scaled to `width` (keeping its
+/// aspect) or to cover the whole image, anchored by `gravity` and shifted by
+/// (`dx`, `dy`), then blended with `mode` at `opacity` (0–100), respecting
+/// both images' alpha.
+#[allow(clippy::too_many_arguments)]
+pub fn apply(
+    img: DynamicImage,
+    layer: DynamicImage,
+    mode: BlendMode,
+    opacity: u32,
+    gravity: Gravity,
+    dx: i32,
+    dy: i32,
+    width: Option<Length>,
+    cover: bool,
+) -> DynamicImage {
+    let opacity = opacity.min(100) as f32 / 100.0;
+    let (bw, bh) = (img.width(), img.height());
+
+    let layer = if cover {
+        let scale = (bw as f64 / layer.width() as f64).max(bh as f64 / layer.height() as f64);
+        let (lw, lh) = ((layer.width() as f64 * scale).round() as u32, (layer.height() as f64 * scale).round() as u32);
+        layer.resize_exact(lw.max(bw), lh.max(bh), FilterType::Lanczos3)
+    } else if let Some(width) = width {
+        let lw = width.resolve(bw).max(1);
+        let lh = ((layer.height() as f64 * lw as f64 / layer.width() as f64).round() as u32).max(1);
+        layer.resize_exact(lw, lh, FilterType::Lanczos3)
+    } else {
+        layer
+    };
+    let layer = layer.to_rgba8();
+
+    // Anchor the smaller box inside the larger one per axis, so an oversized
+    // layer gets a negative offset; dx/dy may push it further off-canvas
+    let (lw, lh) = (layer.width(), layer.height());
+    let (ax, ay) = gravity.offset((bw.max(lw), bh.max(lh)), (bw.min(lw), bh.min(lh)));
+    let ox = if lw <= bw { ax as i64 } else { -(ax as i64) } + dx as i64;
+    let oy = if lh <= bh { ay as i64 } else { -(ay as i64) } + dy as i64;
+
+    let has_alpha = img.color().has_alpha();
+    let mut base: RgbaImage = img.to_rgba8();
+    for (lx, ly, src) in layer.enumerate_pixels() {
+        let (x, y) = (ox + lx as i64, oy + ly as i64);
+        if x < 0 || y < 0 || x >= bw as i64 || y >= bh as i64 {
+            continue;
+        }
+        let dst = base.get_pixel_mut(x as u32, y as u32);
+        let a_s = src[3] as f32 / 255.0 * opacity;
+        if a_s == 0.0 {
+            continue;
+        }
+        let a_b = dst[3] as f32 / 255.0;
+        let cs = [0, 1, 2].map(|c| src[c] as f32 / 255.0);
+        let cb = [0, 1, 2].map(|c| dst[c] as f32 / 255.0);
+        let mixed = blend(mode, cb, cs);
+
+        // W3C compositing: blend where the base exists, then source-over
+        let a_o = a_s + a_b * (1.0 - a_s);
+        for c in 0..3 {
+            let cs_blended = (1.0 - a_b) * cs[c] + a_b * mixed[c].clamp(0.0, 1.0);
+            let co = a_s * cs_blended + a_b * (1.0 - a_s) * cb[c];
+            dst[c] = (co / a_o * 255.0).round().clamp(0.0, 255.0) as u8;
+        }
+        dst[3] = (a_o * 255.0).round() as u8;
+    }
+
+    if has_alpha {
+        DynamicImage::ImageRgba8(base)
+    } else {
+        DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(base).to_rgb8())
+    }
+}
@@ -0,0 +1,76 @@
+use image::{DynamicImage, Rgba, RgbaImage};
+
+use super::watermark::render_text;
+
+/// Description lines shown under each tile before truncating.
+const DESCRIPTION_LINES: usize = 2;
+
+/// One labelled result on the sheet.
+pub struct Tile {
+    pub image: DynamicImage,
+    pub name: String,
+    pub description: String,
+}
+
+/// Greedy word wrap to `max_chars` per line, ending with "…" when the text
+/// needs more than `max_lines` lines.
+fn wrap(text: &str, max_chars: usize, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        let last = &mut lines[max_lines - 1];
+        let keep = last.chars().count().min(max_chars.saturating_sub(1));
+        *last = last.chars().take(keep).collect::<String>().trim_end().to_string() + "…";
+    }
+    lines
+}
+
+/// Lay `tiles` out in a grid of `size`-pixel cells on a dark background, each
+/// with its name and a wrapped description underneath. Without `columns` the
+/// grid is as close to square as possible.
+pub fn apply(tiles: &[Tile], size: u32, columns: Option<u32>) -> DynamicImage {
+    let count = tiles.len().max(1) as u32;
+    let columns = columns.unwrap_or((count as f64).sqrt().ceil() as u32).clamp(1, count);
+    let rows = count.div_ceil(columns);
+
+    let gap = (size / 16).max(8);
+    let name_height = (size as f32 * 0.055).max(10.0);
+    let description_height = (size as f32 * 0.04).max(8.0);
+    let caption = (name_height * 1.6 + description_height * 1.4 * DESCRIPTION_LINES as f32).ceil() as u32;
+    // DejaVu Sans averages a little over half an em per character
+    let max_chars = ((size as f32 / (description_height * 0.55)) as usize).max(8);
+
+    let (cell_w, cell_h) = (size + gap, size + caption + gap);
+    let mut canvas = RgbaImage::from_pixel(columns * cell_w + gap, rows * cell_h + gap, Rgba([30, 30, 30, 255]));
+    for (i, tile) in tiles.iter().enumerate() {
+        let (x, y) = (gap + (i as u32 % columns) * cell_w, gap + (i as u32 / columns) * cell_h);
+        let thumb = tile.image.thumbnail(size, size).to_rgba8();
+        let tx = x + size.saturating_sub(thumb.width()) / 2;
+        let ty = y + size.saturating_sub(thumb.height()) / 2;
+        image::imageops::overlay(&mut canvas, &thumb, tx as i64, ty as i64);
+
+        let name = render_text(&tile.name, None, Rgba([230, 230, 230, 255]), name_height);
+        let text_y = (y + size) as i64 + (name_height * 0.3) as i64;
+        image::imageops::overlay(&mut canvas, &name, x as i64, text_y);
+
+        let lines = wrap(&tile.description, max_chars, DESCRIPTION_LINES);
+        if !lines.is_empty() {
+            let description = render_text(&lines.join("\n"), None, Rgba([150, 150, 150, 255]), description_height);
+            image::imageops::overlay(&mut canvas, &description, x as i64, text_y + (name_height * 1.3) as i64);
+        }
+    }
+    DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(canvas).to_rgb8())
+}
@@ -0,0 +1,172 @@
+use std::path::Path;
+
+use image::{DynamicImage, Rgb};
+
+use crate::utils::{luminance, smoothstep, Aspect, Gravity, Length};
+
+/// Crop a rectangle; lengths are pixels or percent of the image size.
+/// A missing width/height extends to the image edge.
+pub fn apply(img: DynamicImage, x: Length, y: Length, width: Option<Length>, height: Option<Length>) -> DynamicImage {
+    let (w, h) = (img.width(), img.height());
+    let x = x.resolve(w).min(w - 1);
+    let y = y.resolve(h).min(h - 1);
+    let cw = width.map_or(w - x, |l| l.resolve(w)).clamp(1, w - x);
+    let ch = height.map_or(h - y, |l| l.resolve(h)).clamp(1, h - y);
+    img.crop_imm(x, y, cw, ch)
+}
+
+/// Largest crop of the given aspect ratio that fits inside `size`.
+pub(crate) fn aspect_size(size: (u32, u32), aspect: Aspect) -> (u32, u32) {
+    let (w, h) = (size.0 as f64, size.1 as f64);
+    if w / h > aspect.0 {
+        (((h * aspect.0).round() as u32).max(1), size.1)
+    } else {
+        (size.0, ((w / aspect.0).round() as u32).max(1))
+    }
+}
+
+/// Crop to the largest window with the given aspect ratio, anchored by `gravity`.
+pub fn apply_aspect(img: DynamicImage, aspect: Aspect, gravity: Gravity) -> DynamicImage {
+    let (cw, ch) = aspect_size((img.width(), img.height()), aspect);
+    let (x, y) = gravity.offset((img.width(), img.height()), (cw, ch));
+    img.crop_imm(x, y, cw, ch)
+}
+
+/// Longest side of the analysis copy used for smart cropping.
+const ANALYSIS_SIZE: u32 = 256;
+
+/// Per-pixel interest: edge energy, saturation and skin-tone likelihood.
+fn interest_map(img: &DynamicImage) -> (Vec<f64>, u32, u32) {
+    let small = img.thumbnail(ANALYSIS_SIZE, ANALYSIS_SIZE).blur(1.0).to_rgb8();
+    let (w, h) = small.dimensions();
+    let lum: Vec<f64> = small
+        .pixels()
+        .map(|p| luminance(p[0] as f64, p[1] as f64, p[2] as f64) / 255.0)
+        .collect();
+
+    let mut map = vec![0.0; (w * h) as usize];
+    for y in 0..h {
+        for x in 0..w {
+            let i = (y * w + x) as usize;
+            let at = |xx: u32, yy: u32| lum[(yy * w + xx) as usize];
+
+            // Central-difference gradient magnitude
+            let gx = at((x + 1).min(w - 1), y) - at(x.saturating_sub(1), y);
+            let gy = at(x, (y + 1).min(h - 1)) - at(x, y.saturating_sub(1));
+            let edges = (gx * gx + gy * gy).sqrt().min(0.5) * 2.0;
+
+            let p = small.get_pixel(x, y);
+            let (r, g, b) = (p[0] as f64, p[1] as f64, p[2] as f64);
+            let max_ch = r.max(g).max(b);
+            let min_ch = r.min(g).min(b);
+            let saturation = if max_ch > 0.0 { (max_ch - min_ch) / max_ch } else { 0.0 };
+
+            // Skin tones cluster tightly in the CbCr plane
+            let cb = 128.0 - 0.168736 * r - 0.331264 * g + 0.5 * b;
+            let cr = 128.0 + 0.5 * r - 0.418688 * g - 0.081312 * b;
+            let skin = (-((cr - 153.0) / 15.0).powi(2) - ((cb - 102.0) / 20.0).powi(2)).exp()
+                * smoothstep(0.15, 0.35, lum[i]);
+
+            map[i] = 0.5 * edges + 0.2 * saturation + 1.0 * skin;
+        }
+    }
+    (map, w, h)
+}
+
+/// Weight for a position inside the crop window (0–1 on each axis): peaks on
+/// the rule-of-thirds intersections and fades out toward the borders.
+fn composition_weight(u: f64, v: f64) -> f64 {
+    let mut thirds: f64 = 0.0;
+    for tx in [1.0 / 3.0, 2.0 / 3.0] {
+        for ty in [1.0 / 3.0, 2.0 / 3.0] {
+            let d2 = (u - tx).powi(2) + (v - ty).powi(2);
+            thirds = thirds.max((-d2 / (2.0 * 0.15 * 0.15)).exp());
+        }
+    }
+    let border = smoothstep(0.0, 0.08, u.min(1.0 - u)) * smoothstep(0.0, 0.08, v.min(1.0 - v));
+    (0.4 + thirds) * (0.3 + 0.7 * border)
+}
+
+/// Find the highest-scoring window of the given aspect ratio, in full-resolution pixels.
+fn smart_window(img: &DynamicImage, aspect: Aspect) -> (u32, u32, u32, u32) {
+    let (cw, ch) = aspect_size((img.width(), img.height()), aspect);
+    let (map, mw, mh) = interest_map(img);
+    let scale = mw as f64 / img.width() as f64;
+    let (ww, wh) = (
+        ((cw as f64 * scale).round() as u32).clamp(1, mw),
+        ((ch as f64 * scale).round() as u32).clamp(1, mh),
+    );
+
+    // The same composition weights apply at every offset
+    let weights: Vec<f64> = (0..wh)
+        .flat_map(|y| (0..ww).map(move |x| composition_weight((x as f64 + 0.5) / ww as f64, (y as f64 + 0.5) / wh as f64)))
+        .collect();
+
+    let mut best = (f64::MIN, 0, 0);
+    for oy in 0..=(mh - wh) {
+        for ox in 0..=(mw - ww) {
+            // The largest window only slides along one axis, so this stays cheap
+            let mut score = 0.0;
+            for y in 0..wh {
+                let row = ((oy + y) * mw + ox) as usize;
+                let weights = &weights[(y * ww) as usize..((y + 1) * ww) as usize];
+                score += map[row..row + ww as usize].iter().zip(weights).map(|(m, w)| m * w).sum::<f64>();
+            }
+            if score > best.0 {
+                best = (score, ox, oy);
+            }
+        }
+    }
+
+    let x = ((best.1 as f64 / scale).round() as u32).min(img.width() - cw);
+    let y = ((best.2 as f64 / scale).round() as u32).min(img.height() - ch);
+    (x, y, cw, ch)
+}
+
+/// Dim everything outside the chosen window and outline it with thirds guides.
+fn debug_overlay(img: &DynamicImage, (x, y, cw, ch): (u32, u32, u32, u32)) -> DynamicImage {
+    let mut rgb = img.to_rgb8();
+    let inside = |px: u32, py: u32| px >= x && px < x + cw && py >= y && py < y + ch;
+    for (px, py, pixel) in rgb.enumerate_pixels_mut() {
+        if !inside(px, py) {
+            for c in 0..3 {
+                pixel[c] /= 3;
+            }
+        }
+    }
+
+    let line = Rgb([255, 100, 100]);
+    let guide = Rgb([255, 255, 255]);
+    for i in 0..cw {
+        for t in 0..2 {
+            rgb.put_pixel(x + i, (y + t).min(y + ch - 1), line);
+            rgb.put_pixel(x + i, (y + ch - 1).saturating_sub(t).max(y), line);
+        }
+        for third in [ch / 3, 2 * ch / 3] {
+            rgb.put_pixel(x + i, y + third, guide);
+        }
+    }
+    for j in 0..ch {
+        for t in 0..2 {
+            rgb.put_pixel((x + t).min(x + cw - 1), y + j, line);
+            rgb.put_pixel((x + cw - 1).saturating_sub(t).max(x), y + j, line);
+        }
+        for third in [cw / 3, 2 * cw / 3] {
+            rgb.put_pixel(x + third, y + j, guide);
+        }
+    }
+    DynamicImage::ImageRgb8(rgb)
+}
+
+/// Crop to the aspect-ratio window with the most visual interest. With `debug`,
+/// also write an overlay of the chosen window to that path.
+pub fn apply_smart(img: DynamicImage, aspect: Aspect, debug: Option<&Path>) -> DynamicImage {
+    let window = smart_window(&img, aspect);
+    if let Some(path) = debug {
+        debug_overlay(&img, window)
+            .save(path)
+            .unwrap_or_else(|e| panic!("failed to save {}: {e}", path.display()));
+    }
+    let (x, y, cw, ch) = window;
+    img.crop_imm(x, y, cw, ch)
+}
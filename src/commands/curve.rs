@@ -47,8 +47,8 @@ pub(crate) fn cubic_spline_eval(xs: &[f64], coeffs: &[(f64, f64, f64, f64)], x:
         n - 1
     } else {
         let mut i = 0;
-        for j in 1..n {
-            if x < xs[j] {
+        for (j, &xj) in xs.iter().enumerate().take(n).skip(1) {
+            if x < xj {
                 break;
             }
             i = j;
@@ -65,11 +65,10 @@ pub(crate) fn cubic_spline_eval(xs: &[f64], coeffs: &[(f64, f64, f64, f64)], x:
 pub(crate) fn build_curve_lut(xs: &[f64], ys: &[f64]) -> [u8; 256] {
     let coeffs = cubic_spline_coeffs(xs, ys);
     let mut lut = [0u8; 256];
-    for i in 0..256 {
+    for (i, out) in lut.iter_mut().enumerate() {
         let x = i as f64 / 255.0 * 100.0; // map 0-255 to 0-100
         let y = cubic_spline_eval(xs, &coeffs, x);
-        let out = (y / 100.0 * 255.0).round().clamp(0.0, 255.0) as u8;
-        lut[i] = out;
+        *out = (y / 100.0 * 255.0).round().clamp(0.0, 255.0) as u8;
     }
     lut
 }
@@ -1,8 +1,19 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use image::DynamicImage;
 
-pub fn apply(path: &PathBuf) -> DynamicImage {
+/// Camera RAW extensions routed through rawler instead of the `image` decoders.
+const RAW_EXTENSIONS: [&str; 14] =
+    ["arw", "cr2", "cr3", "crw", "dng", "erf", "nef", "nrw", "orf", "pef", "raf", "rw2", "sr2", "srw"];
+
+/// Whether `path` looks like a camera RAW file, by extension.
+pub(crate) fn is_raw(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| RAW_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+pub fn apply(path: &Path) -> DynamicImage {
     let raw_image = rawler::decode_file(path.to_str().unwrap())
         .unwrap_or_else(|e| panic!("failed to decode RAW {}: {e}", path.display()));
     let develop = rawler::imgop::develop::RawDevelop::default();
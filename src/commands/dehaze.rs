@@ -0,0 +1,95 @@
+use image::DynamicImage;
+
+use crate::utils::{guided_filter, keep_alpha, luminance, relative_sigma};
+
+/// Minimum over a (2r+1)x(2r+1) window (separable erosion).
+fn min_filter(src: &[f64], w: usize, h: usize, r: usize) -> Vec<f64> {
+    let mut tmp = vec![0.0; w * h];
+    for y in 0..h {
+        for x in 0..w {
+            let x0 = x.saturating_sub(r);
+            let x1 = (x + r + 1).min(w);
+            tmp[y * w + x] = src[y * w + x0..y * w + x1].iter().cloned().fold(f64::INFINITY, f64::min);
+        }
+    }
+    let mut out = vec![0.0; w * h];
+    for y in 0..h {
+        let y0 = y.saturating_sub(r);
+        let y1 = (y + r + 1).min(h);
+        for x in 0..w {
+            out[y * w + x] = (y0..y1).map(|yy| tmp[yy * w + x]).fold(f64::INFINITY, f64::min);
+        }
+    }
+    out
+}
+
+/// Dark channel prior (He et al.): per-pixel minimum over RGB, then over a local patch.
+fn dark_channel(pixels: &[[f64; 3]], atmosphere: [f64; 3], w: usize, h: usize, r: usize) -> Vec<f64> {
+    let min_rgb: Vec<f64> = pixels
+        .iter()
+        .map(|p| (p[0] / atmosphere[0]).min(p[1] / atmosphere[1]).min(p[2] / atmosphere[2]))
+        .collect();
+    min_filter(&min_rgb, w, h, r)
+}
+
+pub fn apply(img: DynamicImage, amount: i32) -> DynamicImage {
+    if amount == 0 {
+        return img;
+    }
+    let amt = amount.clamp(-100, 100) as f64 / 100.0;
+
+    let mut rgb = img.to_rgb8();
+    let (w, h) = (rgb.width() as usize, rgb.height() as usize);
+    let pixels: Vec<[f64; 3]> = rgb
+        .pixels()
+        .map(|p| [p[0] as f64 / 255.0, p[1] as f64 / 255.0, p[2] as f64 / 255.0])
+        .collect();
+
+    // Patch and refinement radii scale with image dimensions (like structure's sigma)
+    let patch = relative_sigma(rgb.width(), rgb.height(), 10.0, 3.0).round() as usize;
+    let refine = relative_sigma(rgb.width(), rgb.height(), 40.0, 8.0).round() as usize;
+
+    // Atmospheric light: average color of the haziest 0.1% of pixels
+    let dark = dark_channel(&pixels, [1.0, 1.0, 1.0], w, h, patch);
+    let mut order: Vec<usize> = (0..w * h).collect();
+    order.sort_by(|&a, &b| dark[b].total_cmp(&dark[a]));
+    let count = (w * h / 1000).max(1);
+    let mut atmosphere = [0.0; 3];
+    for &i in &order[..count] {
+        for c in 0..3 {
+            atmosphere[c] += pixels[i][c] / count as f64;
+        }
+    }
+    for a in atmosphere.iter_mut() {
+        *a = a.max(0.05);
+    }
+
+    // Transmission estimate, refined with a guided filter on luminance
+    let dark = dark_channel(&pixels, atmosphere, w, h, patch);
+    let omega = 0.95 * amt.abs();
+    let raw_t: Vec<f64> = dark.iter().map(|d| 1.0 - omega * d).collect();
+    let gray: Vec<f64> = pixels
+        .iter()
+        .map(|p| luminance(p[0], p[1], p[2]))
+        .collect();
+    let transmission = guided_filter(&gray, &raw_t, w, h, refine, 1e-3);
+
+    for (i, pixel) in rgb.pixels_mut().enumerate() {
+        let t = transmission[i].clamp(0.0, 1.0);
+        for c in 0..3 {
+            let v = pixels[i][c];
+            let a = atmosphere[c];
+            let new_v = if amt > 0.0 {
+                // Recover scene radiance: J = (I - A) / t + A
+                (v - a) / t.max(0.1) + a
+            } else {
+                // Add haze, more where the scene already looks distant
+                let haze = 1.0 - t;
+                let k = amt.abs() * (0.3 + 0.5 * haze);
+                v * (1.0 - k) + a * k
+            };
+            pixel[c] = (new_v * 255.0).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+    keep_alpha(&img, rgb)
+}
@@ -0,0 +1,223 @@
+use std::path::Path;
+
+use clap::ValueEnum;
+use image::DynamicImage;
+use serde::{Deserialize, Deserializer};
+
+use super::grain::{self, GrainProfile, GrainSeed};
+use crate::utils::{luminance, srgb_to_linear};
+
+/// Stock definitions shipped with imagecli, by name.
+const BUILTIN_STOCKS: [(&str, &str); 5] = [
+    ("portra-400", include_str!("../../stocks/kodak-portra-400.json")),
+    ("ektar-100", include_str!("../../stocks/kodak-ektar-100.json")),
+    ("velvia-50", include_str!("../../stocks/fuji-velvia-50.json")),
+    ("tri-x-400", include_str!("../../stocks/kodak-tri-x-400.json")),
+    ("hp5", include_str!("../../stocks/ilford-hp5.json")),
+];
+
+/// A film stock description, loaded from JSON.
+///
+/// Curves are H&D characteristic curves: `[log10 exposure, density]` points,
+/// with exposure relative to an 18% gray card. `density_range` gives the
+/// densities rendered as black and white (reversed for negatives vs. slides).
+#[derive(Deserialize)]
+pub struct FilmStock {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub curves: StockCurves,
+    pub density_range: [f64; 2],
+    /// Spectral sensitivity (r, g, b) for B&W stocks; color stocks leave it out
+    pub sensitivity: Option<[f64; 3]>,
+    /// Dye cross-talk: rows mix the red/green/blue layer densities
+    #[serde(default = "identity")]
+    pub crosstalk: [[f64; 3]; 3],
+    /// Saturation factor as `[luminance, factor]` points (0–1 luminance)
+    #[serde(default)]
+    pub saturation: Vec<[f64; 2]>,
+    /// Grain profile by name, as in `grain --profile`
+    #[serde(default, deserialize_with = "grain_profile")]
+    pub grain: Option<GrainProfile>,
+}
+
+#[derive(Deserialize)]
+pub struct StockCurves {
+    pub red: Option<Vec<[f64; 2]>>,
+    pub green: Option<Vec<[f64; 2]>>,
+    pub blue: Option<Vec<[f64; 2]>>,
+    pub gray: Option<Vec<[f64; 2]>>,
+}
+
+fn grain_profile<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<GrainProfile>, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    GrainProfile::from_str(&name, false)
+        .map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("unknown grain profile '{name}'")))
+}
+
+fn identity() -> [[f64; 3]; 3] {
+    [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+}
+
+/// Names of the built-in stocks, for help and error messages.
+pub fn builtin_names() -> Vec<&'static str> {
+    BUILTIN_STOCKS.iter().map(|(name, _)| *name).collect()
+}
+
+/// Print each built-in stock's name and description.
+pub fn list_stocks() {
+    for (key, json) in BUILTIN_STOCKS {
+        let stock: FilmStock = serde_json::from_str(json).expect("invalid built-in film stock");
+        println!("{key:<12} {}: {}", stock.name, stock.description);
+    }
+}
+
+/// Load a built-in stock by name, or a stock file from a path.
+pub fn load_stock(stock: &str) -> FilmStock {
+    let json = match BUILTIN_STOCKS.iter().find(|(name, _)| *name == stock) {
+        Some((_, json)) => json.to_string(),
+        None => std::fs::read_to_string(Path::new(stock)).unwrap_or_else(|e| {
+            panic!(
+                "unknown film stock '{stock}' (built-in: {}) and failed to read it as a file: {e}",
+                builtin_names().join(", ")
+            )
+        }),
+    };
+    serde_json::from_str(&json).unwrap_or_else(|e| panic!("invalid film stock '{stock}': {e}"))
+}
+
+/// Monotone cubic (Fritsch–Carlson) interpolation through sorted points,
+/// clamped to the end values outside the curve.
+fn monotone_interpolate(points: &[[f64; 2]], x: f64) -> f64 {
+    let n = points.len();
+    if x <= points[0][0] {
+        return points[0][1];
+    }
+    if x >= points[n - 1][0] {
+        return points[n - 1][1];
+    }
+
+    let slopes: Vec<f64> = points.windows(2).map(|p| (p[1][1] - p[0][1]) / (p[1][0] - p[0][0])).collect();
+    let tangent = |i: usize| -> f64 {
+        if i == 0 {
+            slopes[0]
+        } else if i == n - 1 {
+            slopes[n - 2]
+        } else if slopes[i - 1] * slopes[i] <= 0.0 {
+            0.0
+        } else {
+            // Harmonic mean keeps the segment monotone
+            2.0 / (1.0 / slopes[i - 1] + 1.0 / slopes[i])
+        }
+    };
+
+    let i = points.windows(2).position(|p| x < p[1][0]).unwrap_or(n - 2);
+    let (x0, y0) = (points[i][0], points[i][1]);
+    let (x1, y1) = (points[i + 1][0], points[i + 1][1]);
+    let h = x1 - x0;
+    let t = (x - x0) / h;
+    let (t2, t3) = (t * t, t * t * t);
+    (2.0 * t3 - 3.0 * t2 + 1.0) * y0
+        + (t3 - 2.0 * t2 + t) * h * tangent(i)
+        + (-2.0 * t3 + 3.0 * t2) * y1
+        + (t3 - t2) * h * tangent(i + 1)
+}
+
+/// Piecewise-linear interpolation, clamped to the end values.
+fn linear_interpolate(points: &[[f64; 2]], x: f64) -> f64 {
+    let n = points.len();
+    if x <= points[0][0] {
+        return points[0][1];
+    }
+    if x >= points[n - 1][0] {
+        return points[n - 1][1];
+    }
+    let i = points.windows(2).position(|p| x < p[1][0]).unwrap_or(n - 2);
+    let t = (x - points[i][0]) / (points[i + 1][0] - points[i][0]);
+    points[i][1] + t * (points[i + 1][1] - points[i][1])
+}
+
+fn validated(name: &str, curve: &Option<Vec<[f64; 2]>>) -> Vec<[f64; 2]> {
+    let mut points = curve
+        .clone()
+        .unwrap_or_else(|| panic!("film stock is missing the '{name}' curve"));
+    assert!(points.len() >= 2, "film stock '{name}' curve needs at least two points");
+    points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+    points
+}
+
+/// Log10 exposure relative to 18% gray for a linear value.
+fn log_exposure(linear: f64) -> f64 {
+    (linear.max(1e-4) / 0.18).log10()
+}
+
+/// Render an image through a film stock: scene exposure → per-layer density
+/// (H&D curves) → dye cross-talk → print density range → saturation response,
+/// then the stock's grain unless `grain` is false.
+pub fn apply(img: DynamicImage, stock: &FilmStock, grain: bool, seed: GrainSeed, frame: u64) -> DynamicImage {
+    let [black, white] = stock.density_range;
+    assert!(black != white, "film stock density_range must span a nonzero range");
+    let to_output = |d: f64| ((d - black) / (white - black)).clamp(0.0, 1.0);
+
+    let mut rgb = img.to_rgb8();
+    let result = if let Some(sensitivity) = stock.sensitivity {
+        let curve = validated("gray", &stock.curves.gray);
+        let total: f64 = sensitivity.iter().sum();
+        assert!(total > 0.0, "film stock sensitivity must sum to a positive value");
+        let weights = sensitivity.map(|s| s / total);
+
+        // LUT over linear exposure, sampled finely enough for 8-bit output
+        let lut: Vec<u8> = (0..=4096)
+            .map(|i| {
+                let d = monotone_interpolate(&curve, log_exposure(i as f64 / 4096.0));
+                (to_output(d) * 255.0).round() as u8
+            })
+            .collect();
+        let gray = image::GrayImage::from_fn(rgb.width(), rgb.height(), |x, y| {
+            let p = rgb.get_pixel(x, y);
+            let exposure: f64 = (0..3).map(|c| weights[c] * srgb_to_linear(p[c] as f64 / 255.0)).sum();
+            image::Luma([lut[(exposure.clamp(0.0, 1.0) * 4096.0).round() as usize]])
+        });
+        DynamicImage::ImageLuma8(gray)
+    } else {
+        let curves = [
+            validated("red", &stock.curves.red),
+            validated("green", &stock.curves.green),
+            validated("blue", &stock.curves.blue),
+        ];
+        // Density per layer for every 8-bit input code
+        let density: Vec<[f64; 256]> = curves
+            .iter()
+            .map(|curve| {
+                std::array::from_fn(|v| monotone_interpolate(curve, log_exposure(srgb_to_linear(v as f64 / 255.0))))
+            })
+            .collect();
+        let m = stock.crosstalk;
+
+        for pixel in rgb.pixels_mut() {
+            let d = [0, 1, 2].map(|c| density[c][pixel[c] as usize]);
+            let mixed = [0, 1, 2].map(|c| to_output(m[c][0] * d[0] + m[c][1] * d[1] + m[c][2] * d[2]));
+
+            let lum = luminance(mixed[0], mixed[1], mixed[2]);
+            let factor = if stock.saturation.is_empty() {
+                1.0
+            } else {
+                linear_interpolate(&stock.saturation, lum)
+            };
+            for c in 0..3 {
+                let v = lum + (mixed[c] - lum) * factor;
+                pixel[c] = (v * 255.0).round().clamp(0.0, 255.0) as u8;
+            }
+        }
+        DynamicImage::ImageRgb8(rgb)
+    };
+
+    match (stock.grain, grain) {
+        (Some(profile), true) => {
+            let (amount, size, roughness, monochrome) = profile.settings();
+            grain::apply_poisson(result, amount, size, roughness, monochrome, seed, frame)
+        }
+        _ => result,
+    }
+}
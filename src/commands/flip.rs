@@ -0,0 +1,17 @@
+use clap::ValueEnum;
+use image::DynamicImage;
+
+#[derive(Clone, ValueEnum)]
+pub enum FlipDirection {
+    /// Mirror left-right
+    Horizontal,
+    /// Mirror top-bottom
+    Vertical,
+}
+
+pub fn apply(img: DynamicImage, direction: FlipDirection) -> DynamicImage {
+    match direction {
+        FlipDirection::Horizontal => img.fliph(),
+        FlipDirection::Vertical => img.flipv(),
+    }
+}
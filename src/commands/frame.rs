@@ -0,0 +1,230 @@
+use clap::ValueEnum;
+use image::{DynamicImage, Rgba, RgbaImage};
+
+use super::pad::place_on_canvas;
+use super::watermark::render_text;
+use crate::utils::{Aspect, Gravity, Length};
+
+/// Light showing through sprocket holes and past the film edges in a scan.
+const BACKLIGHT: Rgba<u8> = Rgba([238, 238, 232, 255]);
+
+/// Procedurally drawn frame styles.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum FrameTemplate {
+    /// 35mm negative scan: black rebate, sprocket holes and edge print
+    #[value(name = "35mm")]
+    Film35mm,
+    /// 120 medium format scan: narrow black rebate with edge print
+    #[value(name = "6x6")]
+    MediumFormat,
+    /// Instant print: off-white card with a deep bottom border
+    Instant,
+}
+
+/// Rebate layout in millimetres of film, scaled by the image's short side.
+struct FilmLayout {
+    /// Image height in mm (the short side of the frame)
+    frame: f64,
+    /// Film base above and below the image
+    rebate: f64,
+    /// Gap to the neighbouring frames on the left and right
+    gap: f64,
+    /// Image corner radius
+    corner: f64,
+    sprockets: bool,
+}
+
+const LAYOUT_35MM: FilmLayout = FilmLayout { frame: 24.0, rebate: 5.5, gap: 2.0, corner: 0.3, sprockets: true };
+const LAYOUT_6X6: FilmLayout = FilmLayout { frame: 56.0, rebate: 2.5, gap: 3.0, corner: 0.8, sprockets: false };
+
+/// Backlit margin outside the film edges, in mm.
+const FILM_MARGIN: f64 = 1.0;
+
+impl FrameTemplate {
+    /// Film base or card color when no `--color` is given.
+    fn default_color(self) -> Rgba<u8> {
+        match self {
+            FrameTemplate::Film35mm | FrameTemplate::MediumFormat => Rgba([20, 17, 15, 255]),
+            FrameTemplate::Instant => Rgba([246, 244, 238, 255]),
+        }
+    }
+
+    fn default_label(self) -> Option<&'static str> {
+        match self {
+            FrameTemplate::Film35mm => Some("SAFETY FILM 400"),
+            FrameTemplate::MediumFormat => Some("SAFETY FILM 120"),
+            FrameTemplate::Instant => None,
+        }
+    }
+}
+
+/// Add a border of `top`, `right`, `bottom` and `left` (pixels, or percent of
+/// the shorter side), then optionally pad the result to `aspect`.
+pub fn apply(img: DynamicImage, sides: [Length; 4], color: Rgba<u8>, aspect: Option<Aspect>) -> DynamicImage {
+    let short = img.width().min(img.height());
+    let [top, right, bottom, left] = sides.map(|side| side.resolve(short));
+    let framed = place_on_canvas(&img, img.width() + left + right, img.height() + top + bottom, left, top, color);
+    match aspect {
+        Some(aspect) => pad_to_aspect(framed, aspect, color),
+        None => framed,
+    }
+}
+
+/// Draw a film rebate or instant-print card around the image. `color` replaces
+/// the film base or card color; `label` is the edge print (or instant caption).
+pub fn apply_template(
+    img: DynamicImage,
+    template: FrameTemplate,
+    color: Option<Rgba<u8>>,
+    label: Option<&str>,
+    aspect: Option<Aspect>,
+) -> DynamicImage {
+    let color = color.unwrap_or(template.default_color());
+    let label = label.or(template.default_label()).filter(|l| !l.trim().is_empty());
+    let has_alpha = img.color().has_alpha() || color[3] < 255;
+
+    let canvas = match template {
+        // 35mm runs along the long side, so portrait frames get a vertical strip
+        FrameTemplate::Film35mm if img.height() > img.width() => {
+            let strip = film_rebate(&img.rotate90().to_rgba8(), &LAYOUT_35MM, color, label);
+            image::imageops::rotate270(&strip)
+        }
+        FrameTemplate::Film35mm => film_rebate(&img.to_rgba8(), &LAYOUT_35MM, color, label),
+        FrameTemplate::MediumFormat => film_rebate(&img.to_rgba8(), &LAYOUT_6X6, color, label),
+        FrameTemplate::Instant => instant_card(&img.to_rgba8(), color, label),
+    };
+
+    let framed = if has_alpha {
+        DynamicImage::ImageRgba8(canvas)
+    } else {
+        DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(canvas).to_rgb8())
+    };
+    match aspect {
+        Some(aspect) => pad_to_aspect(framed, aspect, color),
+        None => framed,
+    }
+}
+
+/// Grow the canvas on one axis until it has the given aspect ratio.
+fn pad_to_aspect(img: DynamicImage, aspect: Aspect, color: Rgba<u8>) -> DynamicImage {
+    let (w, h) = (img.width(), img.height());
+    let (cw, ch) = if w as f64 / h as f64 > aspect.0 {
+        (w, ((w as f64 / aspect.0).round() as u32).max(h))
+    } else {
+        (((h as f64 * aspect.0).round() as u32).max(w), h)
+    };
+    let (x, y) = Gravity::Center.offset((cw, ch), (w, h));
+    place_on_canvas(&img, cw, ch, x, y, color)
+}
+
+/// Coverage (0–1) of pixel (`x`, `y`) by a rounded rectangle spanning
+/// `x0..x1` × `y0..y1` with corner radius `r`, antialiased over one pixel.
+fn rounded_rect_coverage(x: u32, y: u32, (x0, y0, x1, y1): (f64, f64, f64, f64), r: f64) -> f32 {
+    let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+    let (cx, cy) = ((x0 + x1) / 2.0, (y0 + y1) / 2.0);
+    let r = r.min((x1 - x0) / 2.0).min((y1 - y0) / 2.0).max(0.0);
+    let qx = (px - cx).abs() - ((x1 - x0) / 2.0 - r);
+    let qy = (py - cy).abs() - ((y1 - y0) / 2.0 - r);
+    let outside = (qx.max(0.0).powi(2) + qy.max(0.0).powi(2)).sqrt();
+    let distance = outside + qx.max(qy).min(0.0) - r;
+    (0.5 - distance).clamp(0.0, 1.0) as f32
+}
+
+fn mix(under: Rgba<u8>, over: Rgba<u8>, t: f32) -> Rgba<u8> {
+    Rgba(std::array::from_fn(|c| (under[c] as f32 + (over[c] as f32 - under[c] as f32) * t).round() as u8))
+}
+
+/// Fill a rounded rectangle (in pixel coordinates) with `color`.
+fn fill_rounded_rect(canvas: &mut RgbaImage, rect: (f64, f64, f64, f64), r: f64, color: Rgba<u8>) {
+    let (x0, y0, x1, y1) = rect;
+    let xs = (x0.floor().max(0.0) as u32)..(x1.ceil().min(canvas.width() as f64) as u32);
+    for y in (y0.floor().max(0.0) as u32)..(y1.ceil().min(canvas.height() as f64) as u32) {
+        for x in xs.clone() {
+            let coverage = rounded_rect_coverage(x, y, rect, r);
+            if coverage > 0.0 {
+                let pixel = canvas.get_pixel_mut(x, y);
+                *pixel = mix(*pixel, color, coverage);
+            }
+        }
+    }
+}
+
+/// Copy `img` onto the canvas at (`ox`, `oy`) with rounded corners.
+fn place_rounded(canvas: &mut RgbaImage, img: &RgbaImage, ox: u32, oy: u32, r: f64) {
+    let rect = (0.0, 0.0, img.width() as f64, img.height() as f64);
+    for (x, y, src) in img.enumerate_pixels() {
+        let coverage = rounded_rect_coverage(x, y, rect, r);
+        let pixel = canvas.get_pixel_mut(ox + x, oy + y);
+        *pixel = mix(*pixel, *src, coverage);
+    }
+}
+
+/// Repeat `label` along a horizontal band centred on `center_y`, starting at `x`,
+/// as many whole times as fit.
+fn edge_print(canvas: &mut RgbaImage, label: &str, x: f64, center_y: f64, height: f32, spacing: f64, color: Rgba<u8>) {
+    let text = render_text(label, None, color, height);
+    let step = text.width() as f64 + spacing;
+    let y = (center_y - text.height() as f64 / 2.0).round() as i64;
+    let mut x = x;
+    while x + text.width() as f64 <= canvas.width() as f64 {
+        image::imageops::overlay(canvas, &text, x.round() as i64, y);
+        x += step;
+    }
+}
+
+/// Lay the image into a scanned film strip: film base above and below, sprocket
+/// holes for 35mm, edge print, and backlight past the film edges.
+fn film_rebate(img: &RgbaImage, layout: &FilmLayout, base: Rgba<u8>, label: Option<&str>) -> RgbaImage {
+    let (w, h) = img.dimensions();
+    let mm = w.min(h) as f64 / layout.frame;
+    let margin = (FILM_MARGIN * mm).round() as u32;
+    let rebate = (layout.rebate * mm).round() as u32;
+    let gap = (layout.gap * mm).round() as u32;
+    let (cw, ch) = (w + 2 * gap, h + 2 * (rebate + margin));
+
+    let mut canvas = RgbaImage::from_pixel(cw, ch, BACKLIGHT);
+    let film = (0.0, margin as f64, cw as f64, (ch - margin) as f64);
+    fill_rounded_rect(&mut canvas, film, 0.0, base);
+    place_rounded(&mut canvas, img, gap, margin + rebate, layout.corner * mm);
+
+    let film_top = margin as f64;
+    let film_bottom = (ch - margin) as f64;
+    let ink = Rgba([232, 150, 52, base[3]]);
+    if layout.sprockets {
+        // KS perforations: 2.8 × 1.98 mm, 4.75 mm pitch, 2 mm in from each edge
+        let (hole_w, hole_h, pitch, inset) = (2.8 * mm, 1.98 * mm, 4.75 * mm, 2.0 * mm);
+        let mut x = (pitch - hole_w) / 2.0;
+        while x < cw as f64 {
+            for y in [film_top + inset, film_bottom - inset - hole_h] {
+                fill_rounded_rect(&mut canvas, (x, y, x + hole_w, y + hole_h), 0.5 * mm, BACKLIGHT);
+            }
+            x += pitch;
+        }
+        // Edge print sits between the lower holes and the film edge
+        if let Some(label) = label {
+            edge_print(&mut canvas, label, gap as f64 + 3.0 * mm, film_bottom - inset / 2.0, (1.2 * mm) as f32, 30.0 * mm, ink);
+        }
+    } else if let Some(label) = label {
+        let center = film_top + layout.rebate * mm / 2.0;
+        edge_print(&mut canvas, label, gap as f64 + 4.0 * mm, center, (1.4 * mm) as f32, 40.0 * mm, ink);
+    }
+    canvas
+}
+
+/// Mount the image on an instant-print card (proportions of an 88 × 107 mm
+/// print with a 79 mm wide image), with an optional caption in the bottom border.
+fn instant_card(img: &RgbaImage, card: Rgba<u8>, caption: Option<&str>) -> RgbaImage {
+    let (w, h) = img.dimensions();
+    let mm = w as f64 / 79.0;
+    let (side, top, bottom) = ((4.5 * mm).round() as u32, (6.0 * mm).round() as u32, (22.0 * mm).round() as u32);
+    let mut canvas = RgbaImage::from_pixel(w + 2 * side, h + top + bottom, card);
+    image::imageops::overlay(&mut canvas, img, side as i64, top as i64);
+
+    if let Some(caption) = caption {
+        let text = render_text(caption, None, Rgba([60, 60, 64, card[3]]), (5.0 * mm) as f32);
+        let x = (canvas.width() as i64 - text.width() as i64) / 2;
+        let y = (top + h) as i64 + (bottom as i64 - text.height() as i64) / 2;
+        image::imageops::overlay(&mut canvas, &text, x, y);
+    }
+    canvas
+}
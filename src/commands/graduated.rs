@@ -0,0 +1,14 @@
+use image::DynamicImage;
+
+use super::local::{adjust, LocalAdjustments};
+use crate::mask::{self, MaskShape};
+
+/// Graduated filter: full adjustment at `start`, fading out by `end` (both
+/// 0–100 along the gradient direction; `angle` 90 runs top to bottom).
+pub fn apply(img: DynamicImage, angle: f64, start: f64, end: f64, adj: &LocalAdjustments) -> DynamicImage {
+    // The linear mask ramps up from start to end, so swap them to fade out instead
+    let shape = MaskShape::Linear { angle, start: end, end: start };
+    let weights = mask::shape_mask(&shape, &img);
+    let adjusted = adjust(img.clone(), adj);
+    mask::blend(&img, adjusted, Some(&weights), 1.0)
+}
@@ -1,6 +1,7 @@
+use clap::ValueEnum;
 use image::DynamicImage;
 
-use crate::utils::smoothstep;
+use crate::utils::{luminance, smoothstep};
 
 /// Deterministic hash-based noise: integer bit-mixing to produce [-1, 1].
 fn hash(x: i64, y: i64, seed: u64) -> f64 {
@@ -41,7 +42,68 @@ fn value_noise(x: f64, y: f64, cell_size: f64, seed: u64) -> f64 {
     top + sy * (bot - top)
 }
 
-pub fn apply(img: DynamicImage, amount: u32, size: u32, roughness: u32, monochrome: bool) -> DynamicImage {
+/// Where the grain pattern's seed comes from.
+pub enum GrainSeed {
+    /// The classic fixed pattern
+    Default,
+    /// A user-supplied seed
+    Fixed(u64),
+    /// A hash of the input pixels, so each image gets its own pattern
+    Content,
+}
+
+impl GrainSeed {
+    /// Seed from the `--seed` / `--seed-from-content` flags.
+    pub fn from_args(seed: Option<u64>, from_content: bool) -> Self {
+        match (seed, from_content) {
+            (Some(s), _) => GrainSeed::Fixed(s),
+            (None, true) => GrainSeed::Content,
+            (None, false) => GrainSeed::Default,
+        }
+    }
+}
+
+/// SplitMix64 finalizer: spreads nearby inputs across the whole 64-bit range.
+fn mix64(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// FNV-1a hash of the image dimensions and pixel data.
+fn content_hash(img: &DynamicImage) -> u64 {
+    let mut h: u64 = 0xcbf29ce484222325;
+    let dims = [img.width().to_le_bytes(), img.height().to_le_bytes()].concat();
+    for &byte in dims.iter().chain(img.as_bytes()) {
+        h ^= byte as u64;
+        h = h.wrapping_mul(0x100000001b3);
+    }
+    h
+}
+
+/// Per-channel seeds. The default seed on frame 0 keeps the classic pattern;
+/// anything else derives independent seeds from (seed, frame, channel).
+fn channel_seeds(seed: GrainSeed, frame: u64, img: &DynamicImage) -> [u64; 3] {
+    let base = match seed {
+        GrainSeed::Default if frame == 0 => return [42, 137, 251],
+        GrainSeed::Default => 42,
+        GrainSeed::Fixed(s) => s,
+        GrainSeed::Content => content_hash(img),
+    };
+    let frame_base = mix64(base ^ mix64(frame));
+    [0, 1, 2].map(|c| mix64(frame_base.wrapping_add(c)))
+}
+
+pub fn apply(
+    img: DynamicImage,
+    amount: u32,
+    size: u32,
+    roughness: u32,
+    monochrome: bool,
+    seed: GrainSeed,
+    frame: u64,
+) -> DynamicImage {
     let amount = amount.clamp(0, 100);
     let size = size.clamp(0, 100);
     let roughness = roughness.clamp(0, 100);
@@ -50,22 +112,19 @@ pub fn apply(img: DynamicImage, amount: u32, size: u32, roughness: u32, monochro
     let cell_size = 1.0 + (size as f64 / 100.0) * 4.0;
     let roughness_t = roughness as f64 / 100.0;
 
-    let mut rgb = img.to_rgb8();
-
     // Per-channel seeds and sub-pixel offsets (emulsion layer misalignment)
-    let channel_seeds: [u64; 3] = [42, 137, 251];
+    let channel_seeds = channel_seeds(seed, frame, &img);
     let channel_offsets: [(f64, f64); 3] = [(0.0, 0.0), (0.37, 0.71), (-0.53, 0.29)];
 
     // Monochrome uses a single seed/offset for all channels
-    let mono_seed: u64 = 42;
+    let mono_seed = channel_seeds[0];
+
+    let mut rgb = img.to_rgb8();
 
     for y in 0..rgb.height() {
         for x in 0..rgb.width() {
             let pixel = rgb.get_pixel(x, y);
-            let lum = (0.2126 * pixel[0] as f64
-                + 0.7152 * pixel[1] as f64
-                + 0.0722 * pixel[2] as f64)
-                / 255.0;
+            let lum = luminance(pixel[0] as f64, pixel[1] as f64, pixel[2] as f64) / 255.0;
 
             // Luminance mask: grain peaks in midtones, suppressed in blacks/whites
             let mask = smoothstep(0.0, 0.25, lum) * (1.0 - smoothstep(0.75, 1.0, lum));
@@ -116,3 +175,262 @@ pub fn apply(img: DynamicImage, amount: u32, size: u32, roughness: u32, monochro
 
     DynamicImage::ImageRgb8(rgb)
 }
+
+/// Which grain synthesis model to use.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum GrainModel {
+    /// Value noise blended with per-pixel noise (fast, stylized)
+    Noise,
+    /// Boolean model of Poisson-distributed grains with random radii (Newson et al.)
+    Poisson,
+}
+
+/// Named film stocks for the Poisson grain model.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum GrainProfile {
+    /// Kodak Portra 400: fine, soft color grain
+    #[value(name = "portra-400")]
+    Portra400,
+    /// Kodak Ektar 100: very fine color grain
+    #[value(name = "ektar-100")]
+    Ektar100,
+    /// Kodak Tri-X 400: pronounced, gritty B&W grain
+    #[value(name = "tri-x-400")]
+    TriX400,
+    /// Ilford HP5 Plus 400: coarse but softer B&W grain
+    #[value(name = "hp5")]
+    Hp5,
+    /// Fujichrome Velvia 50: almost invisible slide-film grain
+    #[value(name = "velvia-50")]
+    Velvia50,
+}
+
+impl GrainProfile {
+    /// (amount, size, roughness, monochrome) on the usual 0–100 scales.
+    pub fn settings(self) -> (u32, u32, u32, bool) {
+        match self {
+            GrainProfile::Portra400 => (20, 30, 40, false),
+            GrainProfile::Ektar100 => (12, 15, 35, false),
+            GrainProfile::TriX400 => (30, 40, 70, true),
+            GrainProfile::Hp5 => (25, 50, 55, true),
+            GrainProfile::Velvia50 => (8, 10, 30, false),
+        }
+    }
+}
+
+/// Small deterministic RNG for per-cell grain placement.
+struct CellRng(u64);
+
+impl CellRng {
+    fn new(cx: i64, cy: i64, seed: u64) -> Self {
+        CellRng(mix64(seed ^ mix64((cx as u64) ^ mix64(cy as u64))))
+    }
+
+    /// Uniform in [0, 1).
+    fn next(&mut self) -> f64 {
+        self.0 = mix64(self.0);
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn poisson(&mut self, lambda: f64) -> u32 {
+        if lambda > 30.0 {
+            // Normal approximation for dense cells
+            let (u1, u2) = (self.next().max(1e-12), self.next());
+            let n = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+            return (lambda + n * lambda.sqrt()).round().max(0.0) as u32;
+        }
+        let limit = (-lambda).exp();
+        let mut k = 0;
+        let mut p = self.next();
+        while p > limit {
+            k += 1;
+            p *= self.next();
+        }
+        k
+    }
+
+    fn normal(&mut self) -> f64 {
+        let (u1, u2) = (self.next().max(1e-12), self.next());
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
+
+/// Separable Gaussian blur of a single-channel plane.
+fn gaussian_blur(src: &[f64], w: usize, h: usize, sigma: f64) -> Vec<f64> {
+    let r = (sigma * 3.0).ceil() as i64;
+    let kernel: Vec<f64> = (-r..=r).map(|i| (-(i * i) as f64 / (2.0 * sigma * sigma)).exp()).collect();
+    let norm: f64 = kernel.iter().sum();
+    let pass = |input: &[f64], horizontal: bool| -> Vec<f64> {
+        let mut out = vec![0.0; w * h];
+        for y in 0..h {
+            for x in 0..w {
+                let mut acc = 0.0;
+                for (k, weight) in kernel.iter().enumerate() {
+                    let d = k as i64 - r;
+                    let (sx, sy) = if horizontal {
+                        ((x as i64 + d).clamp(0, w as i64 - 1) as usize, y)
+                    } else {
+                        (x, (y as i64 + d).clamp(0, h as i64 - 1) as usize)
+                    };
+                    acc += weight * input[sy * w + sx];
+                }
+                out[y * w + x] = acc / norm;
+            }
+        }
+        out
+    };
+    pass(&pass(src, true), false)
+}
+
+/// Supersampling factor per axis used to rasterize grains.
+const GRAIN_SUPERSAMPLE: usize = 4;
+
+/// Most grain centers per pixel: one per supersampled cell. Brighter or finer
+/// areas that would need more get fewer, larger grains with the same expected
+/// coverage, which keeps the work per pixel bounded.
+const GRAIN_MAX_DENSITY: f64 = (GRAIN_SUPERSAMPLE * GRAIN_SUPERSAMPLE) as f64;
+
+/// Image rows rendered at a time, so the supersampled buffer stays small.
+const GRAIN_BAND_ROWS: usize = 64;
+
+/// Grain density (centers per pixel) and base radius for gray level `u`.
+fn grain_density(u: f64, mean_radius: f64, spread: f64) -> (f64, f64) {
+    // Coarser grains in the shadows, finer in the highlights
+    let radius = mean_radius * (1.0 + 0.6 * (1.0 - u));
+    let mean_sq = radius * radius * (spread * spread).exp();
+    let lambda = -(1.0 - u).ln() / (std::f64::consts::PI * mean_sq);
+    if lambda > GRAIN_MAX_DENSITY {
+        (GRAIN_MAX_DENSITY, radius * (lambda / GRAIN_MAX_DENSITY).sqrt())
+    } else {
+        (lambda, radius)
+    }
+}
+
+/// Render one plane with the Boolean grain model: in every pixel, grain
+/// centers follow a Poisson process whose density makes the expected covered
+/// area equal the local gray level. Grains have log-normal radii (larger in
+/// the shadows) and are rasterized on a supersampled grid, so the returned
+/// per-pixel coverage fluctuates around the input with film-like structure.
+///
+/// Bands of `GRAIN_BAND_ROWS` rows are rendered and blurred one at a time,
+/// each re-seeding the grains that reach into it from neighbouring rows.
+fn render_poisson_plane(plane: &[f64], w: usize, h: usize, mean_radius: f64, spread: f64, blur: f64, seed: u64) -> Vec<f64> {
+    let ss = GRAIN_SUPERSAMPLE;
+    let sw = w * ss;
+    let max_radius = mean_radius * (3.0 * spread).exp();
+    // Density grows with the gray level, so the clamped maximum has the largest grains
+    let max_scale = grain_density(0.98, mean_radius, spread).1 / (mean_radius * (1.0 + 0.6 * 0.02));
+    let reach = (max_radius * max_scale).ceil() as usize + 1;
+    let blur_rows = (blur * 3.0).ceil() as usize;
+
+    let mut out = vec![0.0; w * h];
+    for y0 in (0..h).step_by(GRAIN_BAND_ROWS) {
+        let y1 = (y0 + GRAIN_BAND_ROWS).min(h);
+        // Coverage rows [cy0, cy1) include the blur margin
+        let (cy0, cy1) = (y0.saturating_sub(blur_rows), (y1 + blur_rows).min(h));
+        let (sy_min, sy_max) = (cy0 * ss, cy1 * ss - 1);
+        let mut covered = vec![false; sw * (cy1 - cy0) * ss];
+
+        for y in cy0.saturating_sub(reach)..(cy1 + reach).min(h) {
+            for x in 0..w {
+                let u = plane[y * w + x].clamp(0.0, 0.98);
+                if u == 0.0 {
+                    continue;
+                }
+                let (lambda, radius) = grain_density(u, mean_radius, spread);
+                let mu = radius.ln() - spread * spread / 2.0;
+                let cap = max_radius * radius / (mean_radius * (1.0 + 0.6 * (1.0 - u)));
+
+                let mut rng = CellRng::new(x as i64, y as i64, seed);
+                for _ in 0..rng.poisson(lambda) {
+                    let gx = (x as f64 + rng.next()) * ss as f64;
+                    let gy = (y as f64 + rng.next()) * ss as f64;
+                    let r = (mu + spread * rng.normal()).exp().min(cap) * ss as f64;
+                    let r2 = r * r;
+                    let x0 = (gx - r).floor().max(0.0) as usize;
+                    let x1 = ((gx + r).ceil() as usize).min(sw - 1);
+                    let sy0 = ((gy - r).floor().max(0.0) as usize).max(sy_min);
+                    let sy1 = ((gy + r).ceil() as usize).min(sy_max);
+                    for sy in sy0..=sy1 {
+                        let dy = sy as f64 + 0.5 - gy;
+                        let row = (sy - sy_min) * sw;
+                        for sx in x0..=x1 {
+                            let dx = sx as f64 + 0.5 - gx;
+                            if dx * dx + dy * dy <= r2 {
+                                covered[row + sx] = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Average the supersampled coverage back down to pixels
+        let norm = (ss * ss) as f64;
+        let rows = cy1 - cy0;
+        let coverage: Vec<f64> = (0..w * rows)
+            .map(|i| {
+                let (x, y) = (i % w, i / w);
+                let count = (0..ss)
+                    .flat_map(|dy| (0..ss).map(move |dx| (dx, dy)))
+                    .filter(|&(dx, dy)| covered[(y * ss + dy) * sw + x * ss + dx])
+                    .count();
+                count as f64 / norm
+            })
+            .collect();
+
+        // Optical blur of the enlarger / scanner; the margin rows make the band
+        // interior match a blur of the whole plane
+        let blurred = gaussian_blur(&coverage, w, rows, blur);
+        out[y0 * w..y1 * w].copy_from_slice(&blurred[(y0 - cy0) * w..(y1 - cy0) * w]);
+    }
+    out
+}
+
+/// Physically based grain (Boolean model). `size` sets the mean grain radius,
+/// `roughness` the grain sharpness and radius uniformity, `amount` the blend.
+pub fn apply_poisson(
+    img: DynamicImage,
+    amount: u32,
+    size: u32,
+    roughness: u32,
+    monochrome: bool,
+    seed: GrainSeed,
+    frame: u64,
+) -> DynamicImage {
+    let strength = amount.min(100) as f64 / 100.0;
+    let size_t = size.min(100) as f64 / 100.0;
+    let roughness_t = roughness.min(100) as f64 / 100.0;
+
+    let mean_radius = 0.15 + size_t * 0.6;
+    let spread = 0.1 + 0.4 * roughness_t;
+    let blur = 0.3 + 0.6 * (1.0 - roughness_t);
+
+    let seeds = channel_seeds(seed, frame, &img);
+    let mut rgb = img.to_rgb8();
+    let (w, h) = (rgb.width() as usize, rgb.height() as usize);
+
+    if monochrome {
+        let lum: Vec<f64> = rgb
+            .pixels()
+            .map(|p| luminance(p[0] as f64, p[1] as f64, p[2] as f64) / 255.0)
+            .collect();
+        let grain = render_poisson_plane(&lum, w, h, mean_radius, spread, blur, seeds[0]);
+        for (i, pixel) in rgb.pixels_mut().enumerate() {
+            let delta = (grain[i] - lum[i]) * strength * 255.0;
+            for c in 0..3 {
+                pixel[c] = (pixel[c] as f64 + delta).round().clamp(0.0, 255.0) as u8;
+            }
+        }
+    } else {
+        for (c, &seed) in seeds.iter().enumerate() {
+            let plane: Vec<f64> = rgb.pixels().map(|p| p[c] as f64 / 255.0).collect();
+            let grain = render_poisson_plane(&plane, w, h, mean_radius, spread, blur, seed);
+            for (i, pixel) in rgb.pixels_mut().enumerate() {
+                let v = plane[i] + (grain[i] - plane[i]) * strength;
+                pixel[c] = (v * 255.0).round().clamp(0.0, 255.0) as u8;
+            }
+        }
+    }
+    DynamicImage::ImageRgb8(rgb)
+}
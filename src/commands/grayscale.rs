@@ -1,5 +1,54 @@
-use image::DynamicImage;
+use clap::ValueEnum;
+use image::{DynamicImage, GrayImage, GrayAlphaImage};
 
-pub fn apply(img: DynamicImage) -> DynamicImage {
-    img.grayscale()
+/// Classic contrast filters used with panchromatic B&W film.
+#[derive(Clone, ValueEnum)]
+pub enum GrayFilter {
+    /// Dramatic dark skies, light skin
+    Red,
+    /// Darker skies, smoother skin
+    Orange,
+    /// Slightly darkened skies (the usual landscape filter)
+    Yellow,
+    /// Lighter foliage, darker skin
+    Green,
+    /// Lighter skies, emphasized haze
+    Blue,
+}
+
+impl GrayFilter {
+    /// Channel weights (r, g, b) in percent, summing to 100.
+    fn weights(&self) -> [i32; 3] {
+        match self {
+            GrayFilter::Red => [80, 20, 0],
+            GrayFilter::Orange => [60, 35, 5],
+            GrayFilter::Yellow => [45, 45, 10],
+            GrayFilter::Green => [20, 70, 10],
+            GrayFilter::Blue => [10, 20, 70],
+        }
+    }
+}
+
+pub fn apply(img: DynamicImage, weights: Option<[i32; 3]>, filter: Option<GrayFilter>) -> DynamicImage {
+    let weights = match (weights, filter) {
+        (Some(w), _) => w,
+        (None, Some(f)) => f.weights(),
+        (None, None) => return img.grayscale(),
+    };
+    let [wr, wg, wb] = weights.map(|v| v.clamp(-200, 200) as f64 / 100.0);
+
+    let rgba = img.to_rgba8();
+    let mix = |x: u32, y: u32| {
+        let p = rgba.get_pixel(x, y);
+        let v = wr * p[0] as f64 + wg * p[1] as f64 + wb * p[2] as f64;
+        v.round().clamp(0.0, 255.0) as u8
+    };
+    // Keep transparency, like the default grayscale conversion
+    if img.color().has_alpha() {
+        DynamicImage::ImageLumaA8(GrayAlphaImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+            image::LumaA([mix(x, y), rgba.get_pixel(x, y)[3]])
+        }))
+    } else {
+        DynamicImage::ImageLuma8(GrayImage::from_fn(rgba.width(), rgba.height(), |x, y| image::Luma([mix(x, y)])))
+    }
 }
@@ -0,0 +1,60 @@
+use clap::ValueEnum;
+use image::{DynamicImage, Rgb32FImage};
+
+use super::color_grade::hue_to_rgb;
+use crate::utils::{luminance, relative_sigma, smoothstep};
+
+/// Kind of glow spread around bright highlights.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum GlowMode {
+    /// Tinted glow from light scattering off the film base (red-orange by default)
+    Halation,
+    /// Neutral glow that keeps the highlight color (lens / diffusion filter bloom)
+    Bloom,
+}
+
+pub fn apply(img: DynamicImage, amount: u32, threshold: u32, radius: u32, hue: u32, mode: GlowMode) -> DynamicImage {
+    // Full strength lets the glow reach twice the highlight energy
+    let gain = amount.min(100) as f32 / 50.0;
+    if gain == 0.0 {
+        return img;
+    }
+    let threshold = threshold.min(100) as f64 / 100.0;
+    // Soft knee above the threshold so the glow doesn't switch on abruptly
+    let knee = threshold + (1.0 - threshold) * 0.5 + 0.02;
+    let tint = hue_to_rgb((hue % 360) as f64);
+
+    // Isolate the highlights that will glow
+    let rgb = img.to_rgb8();
+    let highlights = Rgb32FImage::from_fn(rgb.width(), rgb.height(), |x, y| {
+        let p = rgb.get_pixel(x, y);
+        let (r, g, b) = (p[0] as f64 / 255.0, p[1] as f64 / 255.0, p[2] as f64 / 255.0);
+        let lum = luminance(r, g, b);
+        let weight = smoothstep(threshold, knee, lum);
+        let glow = match mode {
+            GlowMode::Halation => [tint.0 * lum, tint.1 * lum, tint.2 * lum],
+            GlowMode::Bloom => [r, g, b],
+        };
+        image::Rgb(glow.map(|c| (c * weight) as f32))
+    });
+
+    // Scale blur radius to image dimensions (relative to 1080p baseline)
+    let sigma = relative_sigma(rgb.width(), rgb.height(), radius as f32, 1.0);
+    let glow = DynamicImage::ImageRgb32F(highlights).blur(sigma).into_rgb32f();
+
+    // Screen-blend the glow back over the original
+    let mut out = img.to_rgba8();
+    for (pixel, g) in out.pixels_mut().zip(glow.pixels()) {
+        for c in 0..3 {
+            let base = pixel[c] as f32 / 255.0;
+            let light = (g[c] * gain).clamp(0.0, 1.0);
+            let v = 1.0 - (1.0 - base) * (1.0 - light);
+            pixel[c] = (v * 255.0).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+    if img.color().has_alpha() {
+        DynamicImage::ImageRgba8(out)
+    } else {
+        DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(out).to_rgb8())
+    }
+}
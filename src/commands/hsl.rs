@@ -0,0 +1,203 @@
+use clap::Args;
+use image::DynamicImage;
+
+use crate::utils::{keep_alpha, smoothstep, tone_push};
+
+/// Band centers on the hue wheel (degrees): red, orange, yellow, green, aqua, blue, purple, magenta.
+const BAND_CENTERS: [f64; 8] = [0.0, 30.0, 60.0, 120.0, 180.0, 240.0, 270.0, 300.0];
+
+/// Maximum hue rotation (degrees) at a hue slider value of ±100.
+const MAX_HUE_SHIFT: f64 = 30.0;
+
+/// Per-band hue / saturation / luminance sliders (each -100 to 100).
+#[derive(Args, Clone)]
+pub struct HslBands {
+    /// Red hue shift (-100 toward magenta, 100 toward orange)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub red_hue: i32,
+    /// Red saturation (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub red_sat: i32,
+    /// Red luminance (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub red_lum: i32,
+
+    /// Orange hue shift (-100 toward red, 100 toward yellow)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub orange_hue: i32,
+    /// Orange saturation (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub orange_sat: i32,
+    /// Orange luminance (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub orange_lum: i32,
+
+    /// Yellow hue shift (-100 toward orange, 100 toward green)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub yellow_hue: i32,
+    /// Yellow saturation (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub yellow_sat: i32,
+    /// Yellow luminance (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub yellow_lum: i32,
+
+    /// Green hue shift (-100 toward yellow, 100 toward aqua)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub green_hue: i32,
+    /// Green saturation (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub green_sat: i32,
+    /// Green luminance (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub green_lum: i32,
+
+    /// Aqua hue shift (-100 toward green, 100 toward blue)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub aqua_hue: i32,
+    /// Aqua saturation (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub aqua_sat: i32,
+    /// Aqua luminance (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub aqua_lum: i32,
+
+    /// Blue hue shift (-100 toward aqua, 100 toward purple)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub blue_hue: i32,
+    /// Blue saturation (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub blue_sat: i32,
+    /// Blue luminance (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub blue_lum: i32,
+
+    /// Purple hue shift (-100 toward blue, 100 toward magenta)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub purple_hue: i32,
+    /// Purple saturation (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub purple_sat: i32,
+    /// Purple luminance (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub purple_lum: i32,
+
+    /// Magenta hue shift (-100 toward purple, 100 toward red)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub magenta_hue: i32,
+    /// Magenta saturation (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub magenta_sat: i32,
+    /// Magenta luminance (-100 to 100)
+    #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub magenta_lum: i32,
+}
+
+impl HslBands {
+    /// Sliders as `[hue, sat, lum]` per band, in `BAND_CENTERS` order.
+    pub fn to_array(&self) -> [[i32; 3]; 8] {
+        [
+            [self.red_hue, self.red_sat, self.red_lum],
+            [self.orange_hue, self.orange_sat, self.orange_lum],
+            [self.yellow_hue, self.yellow_sat, self.yellow_lum],
+            [self.green_hue, self.green_sat, self.green_lum],
+            [self.aqua_hue, self.aqua_sat, self.aqua_lum],
+            [self.blue_hue, self.blue_sat, self.blue_lum],
+            [self.purple_hue, self.purple_sat, self.purple_lum],
+            [self.magenta_hue, self.magenta_sat, self.magenta_lum],
+        ]
+    }
+}
+
+pub(crate) fn rgb_to_hsl(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return (0.0, 0.0, l);
+    }
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        60.0 * ((g - b) / d).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / d + 2.0)
+    } else {
+        60.0 * ((r - g) / d + 4.0)
+    };
+    (h, s, l)
+}
+
+pub(crate) fn hsl_to_rgb(h: f64, s: f64, l: f64) -> (f64, f64, f64) {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let hp = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (hp % 2.0 - 1.0).abs());
+    let (r, g, b) = match hp as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    (r + m, g + m, b + m)
+}
+
+/// Smooth weights for each band at a given hue. Neighbouring bands cross-fade
+/// with a smoothstep, so the weights always sum to 1.
+fn band_weights(hue: f64) -> [f64; 8] {
+    let mut weights = [0.0; 8];
+    let h = hue.rem_euclid(360.0);
+    for i in 0..8 {
+        let start = BAND_CENTERS[i];
+        let end = if i == 7 { 360.0 } else { BAND_CENTERS[i + 1] };
+        if h >= start && h < end {
+            let t = smoothstep(start, end, h);
+            weights[i] = 1.0 - t;
+            weights[(i + 1) % 8] = t;
+            break;
+        }
+    }
+    weights
+}
+
+pub fn apply(img: DynamicImage, bands: [[i32; 3]; 8]) -> DynamicImage {
+    let bands = bands.map(|b| b.map(|v| v.clamp(-100, 100) as f64 / 100.0));
+    if bands.iter().flatten().all(|&v| v == 0.0) {
+        return img;
+    }
+
+    let mut rgb = img.to_rgb8();
+    for pixel in rgb.pixels_mut() {
+        let (h, s, l) = rgb_to_hsl(
+            pixel[0] as f64 / 255.0,
+            pixel[1] as f64 / 255.0,
+            pixel[2] as f64 / 255.0,
+        );
+        if s == 0.0 {
+            continue;
+        }
+
+        let weights = band_weights(h);
+        let mut hue_shift = 0.0;
+        let mut sat_shift = 0.0;
+        let mut lum_shift = 0.0;
+        for (w, band) in weights.iter().zip(bands.iter()) {
+            hue_shift += w * band[0];
+            sat_shift += w * band[1];
+            lum_shift += w * band[2];
+        }
+
+        let new_h = h + hue_shift * MAX_HUE_SHIFT;
+        let new_s = (s * (1.0 + sat_shift)).clamp(0.0, 1.0);
+        // Near-gray pixels have an unreliable hue, so fade the luminance shift with saturation
+        let new_l = tone_push(l, lum_shift, s);
+
+        let (r, g, b) = hsl_to_rgb(new_h, new_s, new_l);
+        pixel[0] = (r * 255.0).round().clamp(0.0, 255.0) as u8;
+        pixel[1] = (g * 255.0).round().clamp(0.0, 255.0) as u8;
+        pixel[2] = (b * 255.0).round().clamp(0.0, 255.0) as u8;
+    }
+    keep_alpha(&img, rgb)
+}
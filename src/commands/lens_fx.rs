@@ -0,0 +1,70 @@
+use image::{DynamicImage, RgbaImage};
+
+use super::rotate::sample;
+use super::vignette::{centered_uv, shape_distance};
+use crate::utils::relative_sigma;
+
+/// Radial scale for barrel (`k` > 0) or pincushion (`k` < 0) distortion at
+/// normalized distance `d`.
+fn distortion_scale(k: f64, d: f64) -> f64 {
+    1.0 + k * d * d
+}
+
+/// Vintage lens imperfections: lateral chromatic aberration, barrel/pincushion
+/// distortion and a diffusion-filter glow. Radial effects use the same
+/// centered uv and roundness model as `vignette`, so they line up with it.
+pub fn apply(img: DynamicImage, aberration: i32, distortion: i32, glow: u32, roundness: i32) -> DynamicImage {
+    let ca = aberration.clamp(-100, 100) as f64 / 100.0 * 0.01;
+    let k = distortion.clamp(-100, 100) as f64 / 100.0 * 0.3;
+    let glow = glow.min(100) as f32 / 100.0;
+
+    let src = img.to_rgba8();
+    let (w, h) = (src.width() as f64, src.height() as f64);
+    let longest = w.max(h);
+
+    let mut out = if ca == 0.0 && k == 0.0 {
+        src.clone()
+    } else {
+        // Distance is doubled so the middle of the longest edge sits at 1
+        let dist = |uv_x: f64, uv_y: f64| 2.0 * shape_distance(uv_x, uv_y, roundness);
+
+        // Barrel distortion pulls in the corners; zoom so they stay filled
+        let (cx, cy) = centered_uv(0.0, 0.0, w, h);
+        let zoom = if k > 0.0 { 1.0 / distortion_scale(k, dist(cx, cy)) } else { 1.0 };
+
+        RgbaImage::from_fn(src.width(), src.height(), |x, y| {
+            let (uv_x, uv_y) = centered_uv(x as f64, y as f64, w, h);
+            let d = dist(uv_x, uv_y);
+            let base = distortion_scale(k, d) * zoom;
+            // Red is magnified and blue shrunk relative to green
+            let channel_scale = [base * (1.0 + ca * d), base, base * (1.0 - ca * d)];
+
+            let mut pixel = *src.get_pixel(x, y);
+            for (c, scale) in channel_scale.iter().enumerate() {
+                let sx = (uv_x * scale * longest + w / 2.0).clamp(0.0, w - 1.0);
+                let sy = (uv_y * scale * longest + h / 2.0).clamp(0.0, h - 1.0);
+                pixel[c] = sample(&src, sx, sy, pixel)[c];
+            }
+            pixel
+        })
+    };
+
+    if glow > 0.0 {
+        // Diffusion filter: a soft copy that only lightens, so highlights bleed into shadows
+        let sigma = relative_sigma(src.width(), src.height(), 15.0, 2.0);
+        let blurred = DynamicImage::ImageRgba8(out.clone()).blur(sigma).to_rgba8();
+        for (pixel, soft) in out.pixels_mut().zip(blurred.pixels()) {
+            for c in 0..3 {
+                let v = pixel[c] as f32;
+                let lifted = v.max(soft[c] as f32);
+                pixel[c] = (v + (lifted - v) * glow).round().clamp(0.0, 255.0) as u8;
+            }
+        }
+    }
+
+    if img.color().has_alpha() {
+        DynamicImage::ImageRgba8(out)
+    } else {
+        DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(out).to_rgb8())
+    }
+}
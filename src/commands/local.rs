@@ -0,0 +1,39 @@
+use clap::Args;
+use image::DynamicImage;
+
+use super::{basic, color, structure};
+
+/// Adjustments applied inside a local (graduated or radial) filter.
+#[derive(Args, Clone)]
+pub struct LocalAdjustments {
+    /// Exposure in stops (-5 to 5)
+    #[arg(short, long, default_value_t = 0.0, allow_hyphen_values = true, value_parser = crate::utils::float_range(-5.0f32, 5.0))]
+    pub exposure: f32,
+
+    /// White balance: -100 (cool/blue) to 100 (warm/orange)
+    #[arg(short, long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub temperature: i32,
+
+    /// Saturation: -100 (grayscale) to 100 (oversaturated)
+    #[arg(short, long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub saturation: i32,
+
+    /// Clarity (local contrast): -100 (soften) to 100 (crisp)
+    #[arg(short, long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+    pub clarity: i32,
+}
+
+/// Run the adjustment bundle over the whole image; callers blend it back through their gradient.
+pub(crate) fn adjust(img: DynamicImage, adj: &LocalAdjustments) -> DynamicImage {
+    let mut out = img;
+    if adj.exposure != 0.0 {
+        out = basic::apply(out, adj.exposure, 0, 0, 0, 0, 0);
+    }
+    if adj.temperature != 0 || adj.saturation != 0 {
+        out = color::apply(out, adj.temperature, 0, 0, adj.saturation);
+    }
+    if adj.clarity != 0 {
+        out = structure::apply(out, adj.clarity);
+    }
+    out
+}
@@ -1,13 +1,36 @@
+pub mod analyze;
+pub mod basic;
 pub mod blur;
 pub mod channel;
+pub mod channel_mixer;
 pub mod color;
 pub mod color_grade;
+pub mod compare;
+pub mod contact_sheet;
+pub mod composite;
+pub mod crop;
 pub mod curve;
 pub mod decode_raw;
+pub mod dehaze;
+pub mod film;
+pub mod flip;
+pub mod frame;
 pub mod grain;
+pub mod graduated;
 pub mod grayscale;
+pub mod halation;
+pub mod hsl;
+pub mod lens_fx;
+pub mod local;
+pub mod pad;
+pub mod radial;
 pub mod resize;
+pub mod rotate;
+pub mod shadows_highlights;
 pub mod show_curve;
+pub mod srcset;
 pub mod structure;
 pub mod unsharpen;
 pub mod vignette;
+pub mod watermark;
+pub mod white_balance;
@@ -0,0 +1,24 @@
+use image::{DynamicImage, Rgba, RgbaImage};
+
+use crate::utils::Gravity;
+
+/// Place the image on a canvas filled with `color`. The canvas never shrinks
+/// below the image size; missing dimensions keep the image's own size.
+pub fn apply(img: DynamicImage, width: Option<u32>, height: Option<u32>, color: Rgba<u8>, gravity: Gravity) -> DynamicImage {
+    let cw = width.unwrap_or(img.width()).max(img.width());
+    let ch = height.unwrap_or(img.height()).max(img.height());
+    let (x, y) = gravity.offset((cw, ch), (img.width(), img.height()));
+    place_on_canvas(&img, cw, ch, x, y, color)
+}
+
+/// Copy `img` onto a new `color`-filled canvas at (x, y). The result keeps an
+/// alpha channel only when the image or the fill needs one.
+pub(crate) fn place_on_canvas(img: &DynamicImage, cw: u32, ch: u32, x: u32, y: u32, color: Rgba<u8>) -> DynamicImage {
+    let mut canvas = RgbaImage::from_pixel(cw, ch, color);
+    image::imageops::overlay(&mut canvas, &img.to_rgba8(), x as i64, y as i64);
+    if color[3] == 255 && !img.color().has_alpha() {
+        DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(canvas).to_rgb8())
+    } else {
+        DynamicImage::ImageRgba8(canvas)
+    }
+}
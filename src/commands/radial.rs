@@ -0,0 +1,28 @@
+use image::DynamicImage;
+
+use super::local::{adjust, LocalAdjustments};
+use crate::mask::{self, MaskShape};
+
+/// Radial filter: full adjustment inside an ellipse centered at (`x`, `y`)
+/// with radii `width` x `height` (percent of the long side), fading out over
+/// `feather` percent beyond it. Uses the `radial:` mask shape.
+#[allow(clippy::too_many_arguments)]
+pub fn apply(
+    img: DynamicImage,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    feather: u32,
+    roundness: i32,
+    invert: bool,
+    adj: &LocalAdjustments,
+) -> DynamicImage {
+    let shape = MaskShape::Radial { x, y, width, height, feather: feather as f64, roundness };
+    let mut weights = mask::shape_mask(&shape, &img);
+    if invert {
+        weights.iter_mut().for_each(|w| *w = 1.0 - *w);
+    }
+    let adjusted = adjust(img.clone(), adj);
+    mask::blend(&img, adjusted, Some(&weights), 1.0)
+}
@@ -1,13 +1,162 @@
-use image::DynamicImage;
-
-pub fn apply(img: DynamicImage, output_size: u32) -> DynamicImage {
-    let longest = img.width().max(img.height());
-    if longest <= output_size {
-        img
-    } else {
-        let scale = output_size as f64 / longest as f64;
-        let nw = (img.width() as f64 * scale).round() as u32;
-        let nh = (img.height() as f64 * scale).round() as u32;
-        img.resize_exact(nw, nh, image::imageops::FilterType::Lanczos3)
+use clap::{Args, ValueEnum};
+use image::imageops::FilterType;
+use image::{DynamicImage, Rgba};
+
+use super::pad::place_on_canvas;
+use crate::utils::{parse_color, Gravity};
+
+/// How `--width`/`--height` boxes are filled.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ResizeMode {
+    /// Scale to fit inside the box, preserving aspect ratio
+    Fit,
+    /// Fit inside the box, then pad to exactly the box size with --background
+    Fill,
+    /// Scale to cover the box, then crop the overflow (anchored by --gravity)
+    Cover,
+    /// Stretch to exactly the box size, ignoring aspect ratio
+    Exact,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ResizeFilter {
+    Nearest,
+    Triangle,
+    CatmullRom,
+    Gaussian,
+    Lanczos3,
+}
+
+impl From<ResizeFilter> for FilterType {
+    fn from(filter: ResizeFilter) -> Self {
+        match filter {
+            ResizeFilter::Nearest => FilterType::Nearest,
+            ResizeFilter::Triangle => FilterType::Triangle,
+            ResizeFilter::CatmullRom => FilterType::CatmullRom,
+            ResizeFilter::Gaussian => FilterType::Gaussian,
+            ResizeFilter::Lanczos3 => FilterType::Lanczos3,
+        }
+    }
+}
+
+#[derive(Args, Clone)]
+pub struct ResizeArgs {
+    /// Target size for the longest side in pixels (no-op if already smaller, unless --upscale)
+    #[arg(
+        short = 's',
+        long,
+        conflicts_with_all = ["width", "height", "scale"],
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub output_size: Option<u32>,
+
+    /// Make --output-size target the shortest side instead of the longest
+    #[arg(long, default_value_t = false, requires = "output_size")]
+    pub shortest: bool,
+
+    /// Target width in pixels
+    #[arg(
+        short,
+        long,
+        conflicts_with = "scale",
+        required_unless_present_any = ["output_size", "height", "scale"],
+        required_if_eq_any = [("mode", "fill"), ("mode", "cover"), ("mode", "exact")],
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub width: Option<u32>,
+
+    /// Target height in pixels
+    #[arg(
+        short = 'H',
+        long,
+        conflicts_with = "scale",
+        required_if_eq_any = [("mode", "fill"), ("mode", "cover"), ("mode", "exact")],
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub height: Option<u32>,
+
+    /// How to fit the image into a --width x --height box
+    #[arg(short, long, value_enum, default_value_t = ResizeMode::Fit)]
+    pub mode: ResizeMode,
+
+    /// Scale by a percentage (e.g. 50% or 200%)
+    #[arg(long, value_parser = parse_scale)]
+    pub scale: Option<f64>,
+
+    /// Allow --output-size and the fit and fill modes to enlarge smaller images
+    #[arg(short, long, default_value_t = false)]
+    pub upscale: bool,
+
+    /// Resampling filter
+    #[arg(short, long, value_enum, default_value_t = ResizeFilter::Lanczos3)]
+    pub filter: ResizeFilter,
+
+    /// Anchor for cover crops and fill padding
+    #[arg(short, long, value_enum, default_value_t = Gravity::Center)]
+    pub gravity: Gravity,
+
+    /// Padding color for fill mode (hex like #RRGGBB[AA], or black/white/gray/transparent)
+    #[arg(short, long, default_value = "white", value_parser = parse_color)]
+    pub background: Rgba<u8>,
+}
+
+/// Parse a scale percentage such as `50%` (the `%` is optional).
+fn parse_scale(s: &str) -> Result<f64, String> {
+    s.trim_end_matches('%')
+        .parse::<f64>()
+        .ok()
+        .filter(|v| *v > 0.0)
+        .map(|v| v / 100.0)
+        .ok_or_else(|| format!("invalid scale: {s}"))
+}
+
+/// Resize by a uniform factor, rounding to whole pixels.
+pub(crate) fn scale_by(img: &DynamicImage, scale: f64, filter: FilterType) -> DynamicImage {
+    let nw = ((img.width() as f64 * scale).round() as u32).max(1);
+    let nh = ((img.height() as f64 * scale).round() as u32).max(1);
+    img.resize_exact(nw, nh, filter)
+}
+
+pub fn apply(img: DynamicImage, args: &ResizeArgs) -> DynamicImage {
+    let filter = FilterType::from(args.filter);
+    let (w, h) = (img.width() as f64, img.height() as f64);
+
+    if let Some(scale) = args.scale {
+        return scale_by(&img, scale, filter);
+    }
+
+    if let Some(output_size) = args.output_size {
+        let side = if args.shortest { w.min(h) } else { w.max(h) };
+        if side <= output_size as f64 && !args.upscale {
+            return img;
+        }
+        return scale_by(&img, output_size as f64 / side, filter);
+    }
+
+    // clap requires a box side here, and both sides outside fit mode
+    let (tw, th) = (args.width.unwrap_or(u32::MAX), args.height.unwrap_or(u32::MAX));
+
+    let fit_scale = (tw as f64 / w).min(th as f64 / h);
+    match args.mode {
+        ResizeMode::Fit => {
+            if fit_scale >= 1.0 && !args.upscale {
+                img
+            } else {
+                scale_by(&img, fit_scale, filter)
+            }
+        }
+        ResizeMode::Fill => {
+            let resized = if fit_scale >= 1.0 && !args.upscale { img } else { scale_by(&img, fit_scale, filter) };
+            let (x, y) = args.gravity.offset((tw, th), (resized.width(), resized.height()));
+            place_on_canvas(&resized, tw, th, x, y, args.background)
+        }
+        ResizeMode::Cover => {
+            let cover_scale = (tw as f64 / w).max(th as f64 / h);
+            let resized = scale_by(&img, cover_scale, filter);
+            let (cw, ch) = (tw.min(resized.width()), th.min(resized.height()));
+            let (x, y) = args.gravity.offset((resized.width(), resized.height()), (cw, ch));
+            resized.crop_imm(x, y, cw, ch)
+        }
+        ResizeMode::Exact => img.resize_exact(tw, th, filter),
     }
 }
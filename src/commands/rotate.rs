@@ -0,0 +1,85 @@
+use image::{DynamicImage, Rgba, RgbaImage};
+
+/// Bilinear sample with `fill` outside the image.
+pub(crate) fn sample(src: &RgbaImage, x: f64, y: f64, fill: Rgba<u8>) -> Rgba<u8> {
+    let (w, h) = (src.width() as i64, src.height() as i64);
+    let x0 = x.floor() as i64;
+    let y0 = y.floor() as i64;
+    let fx = x - x0 as f64;
+    let fy = y - y0 as f64;
+    let px = |xx: i64, yy: i64| {
+        if xx < 0 || yy < 0 || xx >= w || yy >= h {
+            fill
+        } else {
+            *src.get_pixel(xx as u32, yy as u32)
+        }
+    };
+    let (p00, p10, p01, p11) = (px(x0, y0), px(x0 + 1, y0), px(x0, y0 + 1), px(x0 + 1, y0 + 1));
+    let mut out = [0u8; 4];
+    for c in 0..4 {
+        let top = p00[c] as f64 + fx * (p10[c] as f64 - p00[c] as f64);
+        let bot = p01[c] as f64 + fx * (p11[c] as f64 - p01[c] as f64);
+        out[c] = (top + fy * (bot - top)).round().clamp(0.0, 255.0) as u8;
+    }
+    Rgba(out)
+}
+
+/// Largest axis-aligned rectangle inside a `w`x`h` image rotated by `theta` radians.
+fn inscribed_size(w: f64, h: f64, theta: f64) -> (f64, f64) {
+    let (sin, cos) = (theta.sin().abs(), theta.cos().abs());
+    let (long, short) = if w >= h { (w, h) } else { (h, w) };
+    if short <= 2.0 * sin * cos * long || (sin - cos).abs() < 1e-10 {
+        // Half-constrained: two crop corners touch the longer side
+        let x = 0.5 * short;
+        if w >= h { (x / sin, x / cos) } else { (x / cos, x / sin) }
+    } else {
+        // Fully constrained: the crop touches all four sides
+        let cos_2a = cos * cos - sin * sin;
+        ((w * cos - h * sin) / cos_2a, (h * cos - w * sin) / cos_2a)
+    }
+}
+
+/// Rotate clockwise by `angle` degrees. Multiples of 90 are lossless; other
+/// angles resample bilinearly and either expand the canvas (filling the
+/// corners with `fill`) or, with `crop`, trim to the largest clean rectangle.
+pub fn apply(img: DynamicImage, angle: f64, crop: bool, fill: Rgba<u8>) -> DynamicImage {
+    let angle = angle.rem_euclid(360.0);
+    if angle % 90.0 == 0.0 {
+        return match angle as u32 {
+            90 => img.rotate90(),
+            180 => img.rotate180(),
+            270 => img.rotate270(),
+            _ => img,
+        };
+    }
+
+    let theta = angle.to_radians();
+    let (w, h) = (img.width() as f64, img.height() as f64);
+    let (sin, cos) = theta.sin_cos();
+    let (out_w, out_h) = if crop {
+        inscribed_size(w, h, theta)
+    } else {
+        (w * cos.abs() + h * sin.abs(), w * sin.abs() + h * cos.abs())
+    };
+    let (out_w, out_h) = ((out_w.floor() as u32).max(1), (out_h.floor() as u32).max(1));
+
+    let src = img.to_rgba8();
+    let (cx, cy) = (w / 2.0, h / 2.0);
+    let (ocx, ocy) = (out_w as f64 / 2.0, out_h as f64 / 2.0);
+    let out = RgbaImage::from_fn(out_w, out_h, |x, y| {
+        // Inverse-rotate each output pixel center back into the source
+        let dx = x as f64 + 0.5 - ocx;
+        let dy = y as f64 + 0.5 - ocy;
+        let sx = cos * dx + sin * dy + cx - 0.5;
+        let sy = -sin * dx + cos * dy + cy - 0.5;
+        sample(&src, sx, sy, fill)
+    });
+
+    let out = DynamicImage::ImageRgba8(out);
+    // Only keep alpha if the source had it or the fill shows through
+    if !img.color().has_alpha() && (crop || fill[3] == 255) {
+        DynamicImage::ImageRgb8(out.to_rgb8())
+    } else {
+        out
+    }
+}
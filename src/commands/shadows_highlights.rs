@@ -0,0 +1,51 @@
+use image::DynamicImage;
+
+use crate::utils::{guided_filter, keep_alpha, luminance, relative_sigma, smoothstep, tone_push};
+
+pub fn apply(img: DynamicImage, shadows: i32, highlights: i32) -> DynamicImage {
+    if shadows == 0 && highlights == 0 {
+        return img;
+    }
+
+    let shadows = shadows.clamp(-100, 100) as f64 / 100.0;
+    let highlights = highlights.clamp(-100, 100) as f64 / 100.0;
+
+    let mut rgb = img.to_rgb8();
+    let (w, h) = (rgb.width() as usize, rgb.height() as usize);
+
+    // Luminance (Rec. 709) on a 0–1 scale
+    let lum: Vec<f64> = rgb
+        .pixels()
+        .map(|p| luminance(p[0] as f64, p[1] as f64, p[2] as f64) / 255.0)
+        .collect();
+
+    // Edge-aware base layer (illumination); the residual is local detail.
+    // Radius scales with image dimensions like structure's blur sigma.
+    let radius = relative_sigma(rgb.width(), rgb.height(), 20.0, 4.0).round() as usize;
+    let base = guided_filter(&lum, &lum, w, h, radius, 0.01);
+
+    for (i, pixel) in rgb.pixels_mut().enumerate() {
+        let b = base[i];
+        let detail = lum[i] - b;
+
+        let shadows_w = 1.0 - smoothstep(0.0, 0.5, b);
+        let highlights_w = smoothstep(0.5, 1.0, b);
+
+        // Positive values push the base toward white, negative toward black
+        let new_b = tone_push(b, shadows, shadows_w);
+        let new_b = tone_push(new_b, highlights, highlights_w);
+
+        let new_lum = (new_b + detail).clamp(0.0, 1.0);
+        let old_lum = lum[i];
+
+        // Shift luminance but keep each channel's offset from it (chroma),
+        // shrinking the chroma when darkening so colors don't invert
+        let chroma_scale = if new_lum < old_lum && old_lum > 0.0 { new_lum / old_lum } else { 1.0 };
+        for c in 0..3 {
+            let chroma = pixel[c] as f64 / 255.0 - old_lum;
+            let new_v = (new_lum + chroma * chroma_scale) * 255.0;
+            pixel[c] = new_v.round().clamp(0.0, 255.0) as u8;
+        }
+    }
+    keep_alpha(&img, rgb)
+}
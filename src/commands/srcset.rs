@@ -0,0 +1,139 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+use serde::Serialize;
+
+use super::resize::scale_by;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SrcsetFormat {
+    Jpeg,
+    Png,
+    Webp,
+    Avif,
+}
+
+impl SrcsetFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            SrcsetFormat::Jpeg => "jpg",
+            SrcsetFormat::Png => "png",
+            SrcsetFormat::Webp => "webp",
+            SrcsetFormat::Avif => "avif",
+        }
+    }
+
+    fn image_format(self) -> ImageFormat {
+        match self {
+            SrcsetFormat::Jpeg => ImageFormat::Jpeg,
+            SrcsetFormat::Png => ImageFormat::Png,
+            SrcsetFormat::Webp => ImageFormat::WebP,
+            SrcsetFormat::Avif => ImageFormat::Avif,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Variant {
+    path: String,
+    format: &'static str,
+    width: u32,
+    height: u32,
+    bytes: u64,
+}
+
+#[derive(Serialize)]
+struct Manifest {
+    source: String,
+    width: u32,
+    height: u32,
+    variants: Vec<Variant>,
+}
+
+/// Expand `{stem}`, `{w}`, `{h}` and `{ext}` in an output path template.
+fn expand_template(template: &str, stem: &str, w: u32, h: u32, ext: &str) -> PathBuf {
+    PathBuf::from(
+        template
+            .replace("{stem}", stem)
+            .replace("{w}", &w.to_string())
+            .replace("{h}", &h.to_string())
+            .replace("{ext}", ext),
+    )
+}
+
+/// Write one variant per width and format. Widths are processed largest first,
+/// each downscaled from the previous variant, so the image is decoded once.
+/// Widths larger than the source are skipped.
+pub fn apply(
+    img: &DynamicImage,
+    widths: &[u32],
+    formats: &[SrcsetFormat],
+    template: &Path,
+    stem: &str,
+    manifest: Option<&PathBuf>,
+) {
+    let template = template.to_str().expect("output template must be valid UTF-8");
+    assert!(
+        widths.len() < 2 || template.contains("{w}"),
+        "srcset output template needs a {{w}} placeholder for multiple widths"
+    );
+    assert!(
+        formats.len() < 2 || template.contains("{ext}"),
+        "srcset output template needs an {{ext}} placeholder for multiple formats"
+    );
+
+    let mut widths: Vec<u32> = widths.to_vec();
+    widths.sort_unstable_by(|a, b| b.cmp(a));
+    widths.dedup();
+
+    let mut variants = Vec::new();
+    let mut current = img.clone();
+    for &w in &widths {
+        if w > img.width() {
+            eprintln!("srcset: skipping {w}px (source is only {}px wide)", img.width());
+            continue;
+        }
+        if w < current.width() {
+            current = scale_by(&current, w as f64 / current.width() as f64, FilterType::Lanczos3);
+        }
+
+        for &format in formats {
+            let path = expand_template(template, stem, current.width(), current.height(), format.extension());
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)
+                    .unwrap_or_else(|e| panic!("failed to create {}: {e}", parent.display()));
+            }
+            // JPEG has no alpha channel
+            let out = match format {
+                SrcsetFormat::Jpeg => DynamicImage::ImageRgb8(current.to_rgb8()),
+                _ => current.clone(),
+            };
+            out.save_with_format(&path, format.image_format())
+                .unwrap_or_else(|e| panic!("failed to save {}: {e}", path.display()));
+
+            let bytes = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            variants.push(Variant {
+                path: path.display().to_string(),
+                format: format.extension(),
+                width: current.width(),
+                height: current.height(),
+                bytes,
+            });
+        }
+    }
+
+    if let Some(manifest_path) = manifest {
+        let manifest = Manifest {
+            source: stem.to_string(),
+            width: img.width(),
+            height: img.height(),
+            variants,
+        };
+        let json = serde_json::to_string_pretty(&manifest).expect("failed to serialize manifest");
+        fs::write(manifest_path, json + "\n")
+            .unwrap_or_else(|e| panic!("failed to write {}: {e}", manifest_path.display()));
+    }
+}
@@ -1,14 +1,14 @@
 use image::{DynamicImage, Rgba};
 
+use crate::utils::relative_sigma;
+
 pub fn apply(img: DynamicImage, amount: i32) -> DynamicImage {
     if amount == 0 {
         return img;
     }
 
     // Scale blur radius to image dimensions (relative to 1080p baseline)
-    let (w, h) = (img.width() as f32, img.height() as f32);
-    let scale = w.min(h) / 1080.0;
-    let sigma = (20.0 * scale).max(4.0);
+    let sigma = relative_sigma(img.width(), img.height(), 20.0, 4.0);
 
     let blurred = img.blur(sigma);
 
@@ -2,6 +2,21 @@ use image::DynamicImage;
 
 use crate::utils::smoothstep;
 
+/// Image coordinates mapped to a centered, aspect-correct frame where the
+/// longest side spans -0.5..0.5.
+pub(crate) fn centered_uv(x: f64, y: f64, w: f64, h: f64) -> (f64, f64) {
+    let longest = w.max(h);
+    ((x / w - 0.5) * (w / longest), (y / h - 0.5) * (h / longest))
+}
+
+/// Distance from the center for a roundness of -100 (rectangular) to 100 (circular).
+pub(crate) fn shape_distance(uv_x: f64, uv_y: f64, roundness: i32) -> f64 {
+    let t = (roundness.clamp(-100, 100) as f64 + 100.0) / 200.0; // 0 = rect, 1 = circle
+    let circle_dist = (uv_x * uv_x + uv_y * uv_y).sqrt();
+    let rect_dist = uv_x.abs().max(uv_y.abs());
+    rect_dist + t * (circle_dist - rect_dist)
+}
+
 pub fn apply(img: DynamicImage, amount: i32, midpoint: u32, roundness: i32, feather: u32) -> DynamicImage {
     let amount = amount.clamp(-100, 100);
     let midpoint = midpoint.clamp(0, 100);
@@ -11,9 +26,7 @@ pub fn apply(img: DynamicImage, amount: i32, midpoint: u32, roundness: i32, feat
     let mut rgb = img.to_rgb8();
     let w = rgb.width() as f64;
     let h = rgb.height() as f64;
-    let longest = w.max(h);
 
-    let t = (roundness as f64 + 100.0) / 200.0; // 0 = rect, 1 = circle
     let radius = midpoint as f64 / 100.0 * 0.75;
     let feather_width = feather as f64 / 100.0 * 0.5;
     let inner = radius;
@@ -22,12 +35,8 @@ pub fn apply(img: DynamicImage, amount: i32, midpoint: u32, roundness: i32, feat
 
     for y in 0..rgb.height() {
         for x in 0..rgb.width() {
-            let uv_x = (x as f64 / w - 0.5) * (w / longest);
-            let uv_y = (y as f64 / h - 0.5) * (h / longest);
-
-            let circle_dist = (uv_x * uv_x + uv_y * uv_y).sqrt();
-            let rect_dist = uv_x.abs().max(uv_y.abs());
-            let dist = rect_dist + t * (circle_dist - rect_dist);
+            let (uv_x, uv_y) = centered_uv(x as f64, y as f64, w, h);
+            let dist = shape_distance(uv_x, uv_y, roundness);
 
             let strength = smoothstep(inner, outer, dist);
 
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont};
+use image::{DynamicImage, Rgba, RgbaImage};
+
+use super::composite::{self, BlendMode};
+use super::rotate;
+use crate::utils::{relative_sigma, Gravity};
+
+/// DejaVu Sans, used when no `--font` is given.
+const BUNDLED_FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans.ttf");
+
+/// What to stamp on the image.
+pub enum Mark<'a> {
+    /// Text, with `{field}` placeholders already expanded
+    Text { text: &'a str, font: Option<&'a Path>, color: Rgba<u8> },
+    /// A logo image, scaled to the mark size
+    Logo(DynamicImage),
+}
+
+/// EXIF fields available as `{placeholders}` in watermark text.
+pub fn exif_fields(path: &Path) -> HashMap<&'static str, String> {
+    let mut fields = HashMap::new();
+    let Ok(file) = File::open(path) else {
+        return fields;
+    };
+    let Ok(exif) = exif::Reader::new().read_from_container(&mut BufReader::new(file)) else {
+        return fields;
+    };
+    let text = |tag: exif::Tag| {
+        exif.get_field(tag, exif::In::PRIMARY)
+            .map(|f| f.display_value().to_string().trim_matches('"').trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+
+    let tags = [
+        ("artist", exif::Tag::Artist),
+        ("copyright", exif::Tag::Copyright),
+        ("make", exif::Tag::Make),
+        ("camera", exif::Tag::Model),
+        ("lens", exif::Tag::LensModel),
+        ("iso", exif::Tag::PhotographicSensitivity),
+        ("focal", exif::Tag::FocalLength),
+        ("aperture", exif::Tag::FNumber),
+        ("shutter", exif::Tag::ExposureTime),
+    ];
+    for (name, tag) in tags {
+        if let Some(v) = text(tag) {
+            fields.insert(name, v);
+        }
+    }
+    // DateTimeOriginal displays as "YYYY-MM-DD HH:MM:SS"
+    if let Some(datetime) = text(exif::Tag::DateTimeOriginal).or_else(|| text(exif::Tag::DateTime)) {
+        let date = datetime.split(' ').next().unwrap_or(&datetime).to_string();
+        fields.insert("year", date.chars().take(4).collect());
+        fields.insert("date", date);
+    }
+    fields
+}
+
+/// Replace `{field}` placeholders. Unknown or missing fields become empty,
+/// with a warning, so a proof never shows raw braces; the spaces around an
+/// empty field collapse so it leaves no gap. Line breaks are kept.
+pub fn expand_placeholders(text: &str, fields: &HashMap<&'static str, String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + len];
+        match fields.get(name).filter(|value| !value.is_empty()) {
+            Some(value) => {
+                out.push_str(&rest[..start]);
+                out.push_str(value);
+                rest = &rest[start + len + 1..];
+            }
+            None => {
+                if !fields.contains_key(name) {
+                    eprintln!("watermark: no EXIF value for {{{name}}}");
+                }
+                // Drop the spaces before the field, and after it too at the start of a line
+                out.push_str(rest[..start].trim_end_matches([' ', '\t']));
+                rest = &rest[start + len + 1..];
+                if out.is_empty() || out.ends_with('\n') {
+                    rest = rest.trim_start_matches([' ', '\t']);
+                }
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Rasterize `text` (one or more lines) at `height` pixels per line.
+pub(crate) fn render_text(text: &str, font: Option<&Path>, color: Rgba<u8>, height: f32) -> RgbaImage {
+    let font = match font {
+        Some(path) => {
+            let data = std::fs::read(path).unwrap_or_else(|e| panic!("failed to read font {}: {e}", path.display()));
+            FontVec::try_from_vec(data).unwrap_or_else(|e| panic!("invalid font {}: {e}", path.display()))
+        }
+        None => FontVec::try_from_vec(BUNDLED_FONT.to_vec()).expect("invalid bundled font"),
+    };
+    let scaled = font.as_scaled(PxScale::from(height));
+    let line_height = scaled.height() + scaled.line_gap();
+
+    // Lay out glyphs line by line, with kerning
+    let mut glyphs = Vec::new();
+    let mut width: f32 = 0.0;
+    let lines: Vec<&str> = text.lines().collect();
+    for (row, line) in lines.iter().enumerate() {
+        let mut caret = 0.0;
+        let mut previous = None;
+        for ch in line.chars() {
+            let id = scaled.glyph_id(ch);
+            if let Some(prev) = previous {
+                caret += scaled.kern(prev, id);
+            }
+            let position = ab_glyph::point(caret, scaled.ascent() + row as f32 * line_height);
+            glyphs.push(id.with_scale_and_position(scaled.scale(), position));
+            caret += scaled.h_advance(id);
+            previous = Some(id);
+        }
+        width = width.max(caret);
+    }
+
+    let pad = (height * 0.1).ceil();
+    let w = (width + 2.0 * pad).ceil().max(1.0) as u32;
+    let h = (line_height * lines.len().max(1) as f32 + 2.0 * pad).ceil() as u32;
+    let mut canvas = RgbaImage::from_pixel(w, h, Rgba([color[0], color[1], color[2], 0]));
+    for glyph in glyphs {
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outlined.px_bounds();
+        outlined.draw(|gx, gy, coverage| {
+            let x = (bounds.min.x + pad) as i64 + gx as i64;
+            let y = (bounds.min.y + pad) as i64 + gy as i64;
+            if x >= 0 && y >= 0 && (x as u32) < w && (y as u32) < h {
+                let pixel = canvas.get_pixel_mut(x as u32, y as u32);
+                let alpha = (coverage.clamp(0.0, 1.0) * color[3] as f32).round() as u8;
+                pixel[3] = pixel[3].max(alpha);
+            }
+        });
+    }
+    canvas
+}
+
+/// Stamp a text or logo mark on the image. `size` and `margin` are pixels at
+/// 1080p, scaled to the short side like `structure` scales its blur.
+#[allow(clippy::too_many_arguments)]
+pub fn apply(
+    img: DynamicImage,
+    mark: Mark,
+    size: f32,
+    gravity: Gravity,
+    margin: f32,
+    opacity: u32,
+    rotation: f64,
+    tile: bool,
+) -> DynamicImage {
+    let (w, h) = (img.width(), img.height());
+    let height = relative_sigma(w, h, size, 6.0);
+    let margin = relative_sigma(w, h, margin, 0.0).round() as i32;
+
+    let mark = match mark {
+        Mark::Text { text, font, color } => render_text(text, font, color, height),
+        Mark::Logo(logo) => {
+            let lw = ((logo.width() as f32 * height / logo.height() as f32).round() as u32).max(1);
+            logo.resize_exact(lw, height.round() as u32, image::imageops::FilterType::Lanczos3).to_rgba8()
+        }
+    };
+    let mark = if rotation % 360.0 != 0.0 {
+        rotate::apply(DynamicImage::ImageRgba8(mark), rotation, false, Rgba([0, 0, 0, 0])).to_rgba8()
+    } else {
+        mark
+    };
+
+    if tile {
+        // Staggered grid of marks with one mark height between them
+        let gap = height.round() as i64;
+        let (step_x, step_y) = (mark.width() as i64 + gap, mark.height() as i64 + gap);
+        let mut layer = RgbaImage::new(w, h);
+        for (row, y) in (0..h as i64).step_by(step_y as usize).enumerate() {
+            let shift = if row % 2 == 1 { step_x / 2 } else { 0 };
+            for x in (-step_x..w as i64).step_by(step_x as usize) {
+                image::imageops::overlay(&mut layer, &mark, x + shift, y + gap / 2);
+            }
+        }
+        let layer = DynamicImage::ImageRgba8(layer);
+        return composite::apply(img, layer, BlendMode::Normal, opacity, Gravity::NorthWest, 0, 0, None, false);
+    }
+
+    // Keep the mark `margin` away from the edges it is anchored to
+    let dx = match gravity {
+        Gravity::West | Gravity::NorthWest | Gravity::SouthWest => margin,
+        Gravity::East | Gravity::NorthEast | Gravity::SouthEast => -margin,
+        _ => 0,
+    };
+    let dy = match gravity {
+        Gravity::North | Gravity::NorthEast | Gravity::NorthWest => margin,
+        Gravity::South | Gravity::SouthEast | Gravity::SouthWest => -margin,
+        _ => 0,
+    };
+    let mark = DynamicImage::ImageRgba8(mark);
+    composite::apply(img, mark, BlendMode::Normal, opacity, gravity, dx, dy, None, false)
+}
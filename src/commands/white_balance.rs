@@ -0,0 +1,270 @@
+use std::str::FromStr;
+
+use clap::ValueEnum;
+use image::DynamicImage;
+
+use crate::utils::{keep_alpha, linear_to_srgb, luminance, srgb_to_linear};
+
+type Mat3 = [[f64; 3]; 3];
+
+/// Linear sRGB (D65) to CIE XYZ.
+const SRGB_TO_XYZ: Mat3 = [
+    [0.4124564, 0.3575761, 0.1804375],
+    [0.2126729, 0.7151522, 0.0721750],
+    [0.0193339, 0.1191920, 0.9503041],
+];
+
+/// CIE XYZ to linear sRGB (D65).
+const XYZ_TO_SRGB: Mat3 = [
+    [3.2404542, -1.5371385, -0.4985314],
+    [-0.9692660, 1.8760108, 0.0415560],
+    [0.0556434, -0.2040259, 1.0572252],
+];
+
+const BRADFORD: Mat3 = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+
+const CAT02: Mat3 = [
+    [0.7328, 0.4296, -0.1624],
+    [-0.7036, 1.6975, 0.0061],
+    [0.0030, 0.0136, 0.9834],
+];
+
+/// Reference temperature: `--kelvin 6500 --tint 0` leaves the image unchanged.
+const REFERENCE_KELVIN: f64 = 6500.0;
+
+/// Chromatic adaptation transform used to move between white points.
+#[derive(Clone, ValueEnum)]
+pub enum Adaptation {
+    Bradford,
+    Cat02,
+}
+
+/// Automatic estimate of the scene white point.
+#[derive(Clone, ValueEnum)]
+pub enum AutoWhite {
+    /// Assume the image averages to neutral gray
+    GrayWorld,
+    /// Assume the brightest pixels are white
+    WhitePatch,
+}
+
+/// A gray region to neutralize, written as `x,y` or `x,y,radius` (pixels).
+#[derive(Clone, Copy)]
+pub struct Sample {
+    pub x: u32,
+    pub y: u32,
+    pub radius: u32,
+}
+
+impl FromStr for Sample {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s
+            .split(',')
+            .map(|v| v.trim().parse::<u32>().map_err(|_| format!("invalid sample: {s}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        match parts[..] {
+            [x, y] => Ok(Sample { x, y, radius: 5 }),
+            [x, y, radius] => Ok(Sample { x, y, radius }),
+            _ => Err(format!("expected x,y or x,y,radius, got {s}")),
+        }
+    }
+}
+
+fn mat_mul(a: &Mat3, b: &Mat3) -> Mat3 {
+    let mut out = [[0.0; 3]; 3];
+    for (i, row) in out.iter_mut().enumerate() {
+        for (j, v) in row.iter_mut().enumerate() {
+            *v = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    out
+}
+
+fn mat_vec(m: &Mat3, v: [f64; 3]) -> [f64; 3] {
+    [0, 1, 2].map(|i| m[i][0] * v[0] + m[i][1] * v[1] + m[i][2] * v[2])
+}
+
+fn mat_inverse(m: &Mat3) -> Mat3 {
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    let cof = |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+    [
+        [cof(1, 2, 1, 2) / det, -cof(0, 2, 1, 2) / det, cof(0, 1, 1, 2) / det],
+        [-cof(1, 2, 0, 2) / det, cof(0, 2, 0, 2) / det, -cof(0, 1, 0, 2) / det],
+        [cof(1, 2, 0, 1) / det, -cof(0, 2, 0, 1) / det, cof(0, 1, 0, 1) / det],
+    ]
+}
+
+/// Planckian locus chromaticity (Kim et al. cubic approximation), 1667–25000 K.
+fn planckian_xy(kelvin: f64) -> (f64, f64) {
+    let t = kelvin.clamp(1667.0, 25000.0);
+    let (t2, t3) = (t * t, t * t * t);
+    let x = if t <= 4000.0 {
+        -0.2661239e9 / t3 - 0.2343589e6 / t2 + 0.8776956e3 / t + 0.179910
+    } else {
+        -3.0258469e9 / t3 + 2.1070379e6 / t2 + 0.2226347e3 / t + 0.240390
+    };
+    let (x2, x3) = (x * x, x * x * x);
+    let y = if t <= 2222.0 {
+        -1.1063814 * x3 - 1.34811020 * x2 + 2.18555832 * x - 0.20219683
+    } else if t <= 4000.0 {
+        -0.9549476 * x3 - 1.37418593 * x2 + 2.09137015 * x - 0.16748867
+    } else {
+        3.0817580 * x3 - 5.87338670 * x2 + 3.75112997 * x - 0.37001483
+    };
+    (x, y)
+}
+
+fn xy_to_uv(x: f64, y: f64) -> (f64, f64) {
+    let d = -2.0 * x + 12.0 * y + 3.0;
+    (4.0 * x / d, 6.0 * y / d)
+}
+
+fn uv_to_xy(u: f64, v: f64) -> (f64, f64) {
+    let d = 2.0 * u - 8.0 * v + 4.0;
+    (3.0 * u / d, 2.0 * v / d)
+}
+
+/// White point (XYZ, Y = 1) for a color temperature, offset off the locus by
+/// `tint` (-1 to 1) along the green-magenta axis in CIE 1960 uv.
+fn kelvin_white(kelvin: f64, tint: f64) -> [f64; 3] {
+    let (u, v) = {
+        let (x, y) = planckian_xy(kelvin);
+        xy_to_uv(x, y)
+    };
+    // Unit normal to the locus, pointing toward green (+v)
+    let (u0, v0) = {
+        let (x, y) = planckian_xy(kelvin - 10.0);
+        xy_to_uv(x, y)
+    };
+    let (u1, v1) = {
+        let (x, y) = planckian_xy(kelvin + 10.0);
+        xy_to_uv(x, y)
+    };
+    let (tu, tv) = (u1 - u0, v1 - v0);
+    let len = (tu * tu + tv * tv).sqrt();
+    let (mut nu, mut nv) = (-tv / len, tu / len);
+    if nv < 0.0 {
+        nu = -nu;
+        nv = -nv;
+    }
+
+    // A greenish illuminant is compensated toward magenta, so +tint moves the source white to green
+    let duv = tint * 0.02;
+    let (x, y) = uv_to_xy(u + nu * duv, v + nv * duv);
+    [x / y, 1.0, (1.0 - x - y) / y]
+}
+
+/// XYZ white point (Y = 1) of a linear sRGB color, or `None` for black.
+fn rgb_white(rgb: [f64; 3]) -> Option<[f64; 3]> {
+    let xyz = linear_to_xyz(rgb);
+    (xyz[1] > 0.0).then(|| xyz.map(|c| c / xyz[1]))
+}
+
+/// CIE XYZ (D65, Y = 1 for white) of a linear sRGB color.
+pub(crate) fn linear_to_xyz(rgb: [f64; 3]) -> [f64; 3] {
+    mat_vec(&SRGB_TO_XYZ, rgb)
+}
+
+/// Correlated color temperature (McCamy's approximation) of a linear sRGB
+/// color, or `None` for black.
+pub(crate) fn correlated_temperature(rgb: [f64; 3]) -> Option<f64> {
+    let xyz = linear_to_xyz(rgb);
+    let sum: f64 = xyz.iter().sum();
+    if sum <= 0.0 {
+        return None;
+    }
+    let (x, y) = (xyz[0] / sum, xyz[1] / sum);
+    let n = (x - 0.3320) / (0.1858 - y);
+    Some((449.0 * n.powi(3) + 3525.0 * n.powi(2) + 6823.3 * n + 5520.33).clamp(1000.0, 25000.0))
+}
+
+/// Von Kries-style adaptation from `src` to `dst` white, as a linear sRGB matrix.
+fn adaptation_matrix(src: [f64; 3], dst: [f64; 3], adaptation: &Adaptation) -> Mat3 {
+    let cone = match adaptation {
+        Adaptation::Bradford => BRADFORD,
+        Adaptation::Cat02 => CAT02,
+    };
+    let s = mat_vec(&cone, src);
+    let d = mat_vec(&cone, dst);
+    let scale = [[d[0] / s[0], 0.0, 0.0], [0.0, d[1] / s[1], 0.0], [0.0, 0.0, d[2] / s[2]]];
+    let cat = mat_mul(&mat_inverse(&cone), &mat_mul(&scale, &cone));
+    mat_mul(&XYZ_TO_SRGB, &mat_mul(&cat, &SRGB_TO_XYZ))
+}
+
+pub fn apply(
+    img: DynamicImage,
+    kelvin: u32,
+    tint: i32,
+    sample: Option<Sample>,
+    auto: Option<AutoWhite>,
+    adaptation: Adaptation,
+) -> DynamicImage {
+    let mut rgb = img.to_rgb8();
+    let linear: Vec<[f64; 3]> = rgb
+        .pixels()
+        .map(|p| [0, 1, 2].map(|c| srgb_to_linear(p[c] as f64 / 255.0)))
+        .collect();
+    let (w, h) = (rgb.width(), rgb.height());
+    let d65 = rgb_white([1.0, 1.0, 1.0]).expect("sRGB white has a positive luminance");
+
+    let (src, dst) = if let Some(s) = sample {
+        let (cx, cy) = (s.x.min(w - 1), s.y.min(h - 1));
+        let r = s.radius;
+        let mut sum = [0.0; 3];
+        let mut n = 0.0;
+        for y in cy.saturating_sub(r)..=(cy + r).min(h - 1) {
+            for x in cx.saturating_sub(r)..=(cx + r).min(w - 1) {
+                let p = linear[(y * w + x) as usize];
+                for c in 0..3 {
+                    sum[c] += p[c];
+                }
+                n += 1.0;
+            }
+        }
+        (rgb_white(sum.map(|v| v / n)), d65)
+    } else if let Some(mode) = auto {
+        let mean = match mode {
+            AutoWhite::GrayWorld => {
+                let n = linear.len() as f64;
+                linear.iter().fold([0.0; 3], |acc, p| [0, 1, 2].map(|c| acc[c] + p[c] / n))
+            }
+            AutoWhite::WhitePatch => {
+                // Average of the brightest 1% of pixels
+                let mut order: Vec<usize> = (0..linear.len()).collect();
+                let lum = |p: &[f64; 3]| luminance(p[0], p[1], p[2]);
+                order.sort_by(|&a, &b| lum(&linear[b]).total_cmp(&lum(&linear[a])));
+                let count = (linear.len() / 100).max(1);
+                order[..count]
+                    .iter()
+                    .fold([0.0; 3], |acc, &i| [0, 1, 2].map(|c| acc[c] + linear[i][c] / count as f64))
+            }
+        };
+        (rgb_white(mean), d65)
+    } else {
+        let tint = tint.clamp(-100, 100) as f64 / 100.0;
+        let kelvin = (kelvin as f64).clamp(2000.0, 25000.0);
+        (Some(kelvin_white(kelvin, tint)), kelvin_white(REFERENCE_KELVIN, 0.0))
+    };
+    // A black reference has no color to neutralize
+    let Some(src) = src else {
+        eprintln!("white-balance: the reference pixels are black, leaving the image unchanged");
+        return img;
+    };
+
+    let m = adaptation_matrix(src, dst, &adaptation);
+    for (pixel, lin) in rgb.pixels_mut().zip(linear.iter()) {
+        let out = mat_vec(&m, *lin);
+        for c in 0..3 {
+            pixel[c] = (linear_to_srgb(out[c].clamp(0.0, 1.0)) * 255.0).round() as u8;
+        }
+    }
+    keep_alpha(&img, rgb)
+}
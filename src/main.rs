@@ -1,13 +1,31 @@
 use std::io::{self, Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use clap::{Parser, Subcommand};
-use image::{DynamicImage, ImageFormat, ImageReader};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand};
+use image::{DynamicImage, ImageFormat, ImageReader, Rgba};
 
 mod commands;
+mod mask;
+mod preset;
 mod utils;
 
 use commands::channel::ChannelColor;
+use commands::compare::{CompareMode, WipeDirection};
+use commands::composite::BlendMode;
+use commands::flip::FlipDirection;
+use commands::frame::FrameTemplate;
+use commands::grain::{GrainModel, GrainProfile, GrainSeed};
+use commands::grayscale::GrayFilter;
+use commands::halation::GlowMode;
+use commands::hsl::HslBands;
+use commands::local::LocalAdjustments;
+use commands::resize::ResizeArgs;
+use commands::srcset::SrcsetFormat;
+use commands::watermark::Mark;
+use commands::white_balance::{Adaptation, AutoWhite, Sample};
+use mask::MaskSpec;
+use utils::{Aspect, Gravity, Length};
 
 #[derive(Parser)]
 #[command(name = "imagecli", about = "A simple image processing CLI")]
@@ -22,6 +40,24 @@ struct Cli {
     /// Output file path (writes PNG to stdout if omitted)
     #[arg(short, long, global = true)]
     output: Option<PathBuf>,
+
+    /// Restrict the command to a region: [+|-|*]luma:min=,max=,soft= | hue:hue=,width=,soft=,min-sat=
+    /// | linear:angle=,start=,end= | radial:x=,y=,radius=,width=,height=,feather=,roundness= | image:<path>.
+    /// Repeat to combine (+ add, - subtract, * intersect)
+    #[arg(long, global = true, allow_hyphen_values = true)]
+    mask: Vec<MaskSpec>,
+
+    /// Invert the combined mask
+    #[arg(long, global = true, default_value_t = false)]
+    mask_invert: bool,
+
+    /// Soften the mask edges by this radius in pixels at 1080p (scaled to the image)
+    #[arg(long, global = true, default_value_t = 0.0)]
+    mask_feather: f32,
+
+    /// Blend the result with the original: 0 keeps the input, 100 is the full effect
+    #[arg(long, global = true, default_value_t = 100, value_parser = clap::value_parser!(u32).range(0..=100))]
+    strength: u32,
 }
 
 #[derive(Subcommand)]
@@ -45,13 +81,152 @@ enum Command {
     },
 
     /// Convert to grayscale (black and white)
-    Grayscale,
+    Grayscale {
+        /// Channel weights in percent, e.g. 30,59,11 (should sum to 100)
+        #[arg(short, long, allow_hyphen_values = true, conflicts_with = "filter", value_parser = utils::int_tuple::<3>(-200, 200))]
+        weights: Option<[i32; 3]>,
 
-    /// Resize image so the longest side equals output_size (no-op if already smaller)
+        /// Emulate a B&W contrast filter
+        #[arg(short, long, value_enum)]
+        filter: Option<GrayFilter>,
+    },
+
+    /// Resize by longest/shortest side, to a width/height box (fit, fill, cover, exact), or by percent
     Resize {
-        /// Target size for the longest side in pixels
-        #[arg(short = 's', long)]
-        output_size: u32,
+        #[command(flatten)]
+        args: ResizeArgs,
+    },
+
+    /// Crop to a rectangle (pixels or percent) or to an aspect ratio
+    Crop {
+        /// Left edge in pixels or percent (e.g. 40 or 10%)
+        #[arg(short, long, default_value = "0", conflicts_with = "aspect")]
+        x: Length,
+
+        /// Top edge in pixels or percent
+        #[arg(short, long, default_value = "0", conflicts_with = "aspect")]
+        y: Length,
+
+        /// Crop width in pixels or percent (defaults to the right edge)
+        #[arg(short, long, conflicts_with = "aspect")]
+        width: Option<Length>,
+
+        /// Crop height in pixels or percent (defaults to the bottom edge)
+        #[arg(short = 'H', long, conflicts_with = "aspect")]
+        height: Option<Length>,
+
+        /// Crop the largest window with this aspect ratio (e.g. 16:9, 1:1, 1.5)
+        #[arg(short, long)]
+        aspect: Option<Aspect>,
+
+        /// Where to anchor the --aspect window
+        #[arg(short, long, value_enum, default_value_t = Gravity::Center, conflicts_with = "smart")]
+        gravity: Gravity,
+
+        /// Pick the --aspect window by content (edges, saturation, skin tones, rule of thirds)
+        #[arg(short, long, default_value_t = false, requires = "aspect")]
+        smart: bool,
+
+        /// Write an overlay showing the chosen --smart window to this path
+        #[arg(long, requires = "smart")]
+        debug: Option<PathBuf>,
+    },
+
+    /// Rotate clockwise: 90/180/270 are lossless, other angles resample (e.g. to straighten)
+    Rotate {
+        /// Angle in degrees, clockwise (negative for counter-clockwise)
+        #[arg(short, long, allow_hyphen_values = true)]
+        angle: f64,
+
+        /// Trim to the largest rectangle without empty corners (for straightening)
+        #[arg(short, long, default_value_t = false)]
+        crop: bool,
+
+        /// Fill color for uncovered corners (hex like #RRGGBB[AA], or black/white/gray/transparent)
+        #[arg(short, long, default_value = "black", value_parser = utils::parse_color)]
+        fill: Rgba<u8>,
+    },
+
+    /// Mirror the image horizontally or vertically
+    Flip {
+        /// Flip direction
+        #[arg(value_enum)]
+        direction: FlipDirection,
+    },
+
+    /// Extend the canvas to a given size, filling the new area with a color
+    #[command(alias = "extend")]
+    Pad {
+        /// Canvas width in pixels (defaults to the image width)
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+        width: Option<u32>,
+
+        /// Canvas height in pixels (defaults to the image height)
+        #[arg(short = 'H', long, value_parser = clap::value_parser!(u32).range(1..))]
+        height: Option<u32>,
+
+        /// Fill color (hex like #RRGGBB[AA], or black/white/gray/transparent)
+        #[arg(short, long, default_value = "white", value_parser = utils::parse_color)]
+        color: Rgba<u8>,
+
+        /// Where to place the image on the canvas
+        #[arg(short, long, value_enum, default_value_t = Gravity::Center)]
+        gravity: Gravity,
+    },
+
+    /// Add a border (uniform or per side), pad to an aspect ratio, or draw a film or instant-print frame
+    Frame {
+        /// Border on every side, in pixels or percent of the shorter side
+        #[arg(short, long, default_value = "0", conflicts_with = "template")]
+        border: Length,
+
+        /// Top border (overrides --border)
+        #[arg(long, conflicts_with = "template")]
+        top: Option<Length>,
+
+        /// Right border (overrides --border)
+        #[arg(long, conflicts_with = "template")]
+        right: Option<Length>,
+
+        /// Bottom border (overrides --border)
+        #[arg(long, conflicts_with = "template")]
+        bottom: Option<Length>,
+
+        /// Left border (overrides --border)
+        #[arg(long, conflicts_with = "template")]
+        left: Option<Length>,
+
+        /// Border color (hex like #RRGGBB[AA], or black/white/gray/transparent);
+        /// defaults to white, or the film base / card color of a template
+        #[arg(short, long, value_parser = utils::parse_color)]
+        color: Option<Rgba<u8>>,
+
+        /// Pad the framed image to this aspect ratio (e.g. 4:5, 1:1)
+        #[arg(short, long)]
+        aspect: Option<Aspect>,
+
+        /// Draw a film rebate or instant-print card instead of a plain border
+        #[arg(short, long, value_enum)]
+        template: Option<FrameTemplate>,
+
+        /// Edge print for film templates, or a caption under an instant print
+        #[arg(short, long, requires = "template")]
+        label: Option<String>,
+    },
+
+    /// Write a responsive image set in one pass; -o is a template with {stem}, {w}, {h}, {ext}
+    Srcset {
+        /// Target widths in pixels (widths above the source width are skipped)
+        #[arg(short, long, value_delimiter = ',', default_values_t = [320, 640, 1280, 2560], value_parser = clap::value_parser!(u32).range(1..))]
+        widths: Vec<u32>,
+
+        /// Output formats
+        #[arg(short, long, value_enum, value_delimiter = ',', default_values_t = [SrcsetFormat::Jpeg])]
+        format: Vec<SrcsetFormat>,
+
+        /// Also write a JSON manifest of variant sizes and byte counts
+        #[arg(short, long)]
+        manifest: Option<PathBuf>,
     },
 
     /// Extract a single RGB channel as a grayscale image
@@ -61,6 +236,25 @@ enum Command {
         color: ChannelColor,
     },
 
+    /// Mix RGB channels through a 3x3 matrix plus constant offsets (values in percent)
+    ChannelMixer {
+        /// Red output from r,g,b sources (-200 to 200 each)
+        #[arg(long, default_value = "100,0,0", allow_hyphen_values = true, value_parser = utils::int_tuple::<3>(-200, 200))]
+        red: [i32; 3],
+
+        /// Green output from r,g,b sources (-200 to 200 each)
+        #[arg(long, default_value = "0,100,0", allow_hyphen_values = true, value_parser = utils::int_tuple::<3>(-200, 200))]
+        green: [i32; 3],
+
+        /// Blue output from r,g,b sources (-200 to 200 each)
+        #[arg(long, default_value = "0,0,100", allow_hyphen_values = true, value_parser = utils::int_tuple::<3>(-200, 200))]
+        blue: [i32; 3],
+
+        /// Constant offset added to r,g,b outputs (-100 to 100 each)
+        #[arg(long, default_value = "0,0,0", allow_hyphen_values = true, value_parser = utils::int_tuple::<3>(-100, 100))]
+        constant: [i32; 3],
+    },
+
     /// Tone curve adjustment via a 5-point spline (values on a 0–100 scale)
     Curve {
         /// Dark point adjustment (input=0)
@@ -84,6 +278,62 @@ enum Command {
         highlights: i32,
     },
 
+    /// Print histograms, percentiles, clipping, saturation, dominant colors and color temperature as JSON (to -o or stdout)
+    Analyze {
+        /// Also save a 256x256 histogram plot to this path
+        #[arg(short, long)]
+        render: Option<PathBuf>,
+
+        /// Number of dominant colors to report
+        #[arg(short = 'n', long, default_value_t = 5)]
+        colors: usize,
+
+        /// Exit with status 1 when more than this percentage of pixels clip in the highlights
+        #[arg(long)]
+        max_clipping: Option<f64>,
+    },
+
+    /// Compare the input with another image: split wipe, side-by-side or difference heatmap;
+    /// prints PSNR, SSIM and ΔE 2000 to stderr
+    Compare {
+        /// Image to compare against the input (file, RAW, or - for stdin); resized to match unless --strict-size
+        other: PathBuf,
+
+        /// How to show the two images
+        #[arg(short, long, value_enum, default_value_t = CompareMode::Split)]
+        mode: CompareMode,
+
+        /// Wipe direction for split mode
+        #[arg(short, long, value_enum, default_value_t = WipeDirection::Vertical)]
+        direction: WipeDirection,
+
+        /// Wipe position for split mode (0–100)
+        #[arg(short, long, default_value_t = 50.0, value_parser = utils::float_range(0.0, 100.0))]
+        position: f64,
+
+        /// Labels for side-by-side mode, comma-separated
+        #[arg(short, long, value_delimiter = ',', default_values = ["Before", "After"])]
+        labels: Vec<String>,
+
+        /// Difference amplification: ΔE 100/amplify shows as white
+        #[arg(short, long, default_value_t = 5.0)]
+        amplify: f64,
+
+        /// Exit with status 1 when the maximum ΔE exceeds this (tolerance checks in scripts and tests)
+        #[arg(long)]
+        max_delta_e: Option<f64>,
+
+        /// Exit with status 1 when the sizes differ instead of resizing the other image
+        #[arg(long, default_value_t = false)]
+        strict_size: bool,
+    },
+
+    /// Work with the JSON pipelines in presets/
+    Preset {
+        #[command(subcommand)]
+        action: PresetAction,
+    },
+
     /// Debug: render the tone curve as a 256x256 plot (no input image needed)
     ShowCurve {
         /// Dark point adjustment (input=0)
@@ -107,107 +357,511 @@ enum Command {
         highlights: i32,
     },
 
+    /// Basic tone panel: exposure, contrast, highlights, shadows, whites, blacks
+    #[command(alias = "exposure")]
+    Basic {
+        /// Exposure in EV stops: -5 to 5 (applied in linear light)
+        #[arg(short, long, default_value_t = 0.0, allow_hyphen_values = true, value_parser = utils::float_range(-5.0f32, 5.0))]
+        exposure: f32,
+
+        /// Contrast around middle gray: -100 (flat) to 100 (punchy)
+        #[arg(short, long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+        contrast: i32,
+
+        /// Highlights: -100 (recover) to 100 (brighten)
+        #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+        highlights: i32,
+
+        /// Shadows: -100 (deepen) to 100 (lift)
+        #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+        shadows: i32,
+
+        /// White clipping point: -100 (dim whites) to 100 (clip more)
+        #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+        whites: i32,
+
+        /// Black clipping point: -100 (crush blacks) to 100 (lift blacks)
+        #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+        blacks: i32,
+    },
+
     /// Adjust color: temperature, tint, vibrance, saturation
     Color {
         /// White balance: -100 (cool/blue) to 100 (warm/orange)
-        #[arg(long, default_value_t = 0, allow_hyphen_values = true)]
+        #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
         temperature: i32,
 
         /// Green-magenta axis: -100 (green) to 100 (magenta)
-        #[arg(long, default_value_t = 0, allow_hyphen_values = true)]
+        #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
         tint: i32,
 
         /// Smart saturation for muted colors: -100 to 100
-        #[arg(long, default_value_t = 0, allow_hyphen_values = true)]
+        #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
         vibrance: i32,
 
         /// Linear saturation: -100 (grayscale) to 100 (oversaturated)
-        #[arg(long, default_value_t = 0, allow_hyphen_values = true)]
+        #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
         saturation: i32,
     },
 
+    /// White balance via chromatic adaptation (Kelvin/tint, gray sample, or auto)
+    WhiteBalance {
+        /// Scene illuminant in Kelvin (2000–25000); lower values cool the image, 6500 is neutral
+        #[arg(short, long, default_value_t = 6500, value_parser = clap::value_parser!(u32).range(2000..=25000))]
+        kelvin: u32,
+
+        /// Green-magenta axis: -100 (green) to 100 (magenta)
+        #[arg(short, long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+        tint: i32,
+
+        /// Neutralize a known gray region: x,y[,radius] in pixels (radius defaults to 5)
+        #[arg(long, conflicts_with_all = ["kelvin", "tint", "auto"])]
+        sample: Option<Sample>,
+
+        /// Estimate the white point automatically
+        #[arg(long, value_enum, conflicts_with_all = ["kelvin", "tint"])]
+        auto: Option<AutoWhite>,
+
+        /// Chromatic adaptation transform
+        #[arg(long, value_enum, default_value_t = Adaptation::Bradford)]
+        adaptation: Adaptation,
+    },
+
     /// Color grading: tint shadows, midtones, and highlights independently
     ColorGrade {
         /// Shadows hue (0–360 degrees on color wheel)
-        #[arg(long, default_value_t = 0)]
+        #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..=360))]
         shadows_hue: u32,
         /// Shadows saturation (0–100, distance from center)
-        #[arg(long, default_value_t = 0)]
+        #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..=100))]
         shadows_sat: u32,
         /// Shadows luminance shift (-100 to +100)
-        #[arg(long, default_value_t = 0, allow_hyphen_values = true)]
+        #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
         shadows_lum: i32,
 
         /// Midtones hue (0–360 degrees on color wheel)
-        #[arg(long, default_value_t = 0)]
+        #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..=360))]
         midtones_hue: u32,
         /// Midtones saturation (0–100, distance from center)
-        #[arg(long, default_value_t = 0)]
+        #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..=100))]
         midtones_sat: u32,
         /// Midtones luminance shift (-100 to +100)
-        #[arg(long, default_value_t = 0, allow_hyphen_values = true)]
+        #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
         midtones_lum: i32,
 
         /// Highlights hue (0–360 degrees on color wheel)
-        #[arg(long, default_value_t = 0)]
+        #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..=360))]
         highlights_hue: u32,
         /// Highlights saturation (0–100, distance from center)
-        #[arg(long, default_value_t = 0)]
+        #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..=100))]
         highlights_sat: u32,
         /// Highlights luminance shift (-100 to +100)
-        #[arg(long, default_value_t = 0, allow_hyphen_values = true)]
+        #[arg(long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
         highlights_lum: i32,
     },
 
+    /// Color mixer: per-hue hue, saturation and luminance for eight hue bands
+    Hsl {
+        #[command(flatten)]
+        bands: HslBands,
+    },
+
     /// Decode a camera RAW file (CR3, NEF, ARW, etc.)
     DecodeRaw,
 
+    /// Glow around bright highlights: red-orange film halation or neutral bloom
+    Halation {
+        /// Glow strength (0–100)
+        #[arg(short, long, default_value_t = 50, value_parser = clap::value_parser!(u32).range(0..=100))]
+        amount: u32,
+
+        /// Luminance above which highlights start to glow (0–100)
+        #[arg(short, long, default_value_t = 70, value_parser = clap::value_parser!(u32).range(0..=100))]
+        threshold: u32,
+
+        /// Glow radius in pixels at 1080p (scaled to the image size)
+        #[arg(short, long, default_value_t = 30)]
+        radius: u32,
+
+        /// Halation tint hue in degrees (0–360, ignored in bloom mode)
+        #[arg(long, default_value_t = 15, value_parser = clap::value_parser!(u32).range(0..=360))]
+        hue: u32,
+
+        /// Tinted film halation or neutral bloom
+        #[arg(short, long, value_enum, default_value_t = GlowMode::Halation)]
+        mode: GlowMode,
+    },
+
+    /// Blend a second image (texture, light leak, logo) over the input
+    Composite {
+        /// Layer image: a file, a camera RAW file, or - for stdin (then the base needs -i)
+        layer: PathBuf,
+
+        /// Blend mode
+        #[arg(short, long, value_enum, default_value_t = BlendMode::Normal)]
+        mode: BlendMode,
+
+        /// Layer opacity (0–100)
+        #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(0..=100))]
+        opacity: u32,
+
+        /// Where to anchor the layer
+        #[arg(short, long, value_enum, default_value_t = Gravity::Center)]
+        gravity: Gravity,
+
+        /// Horizontal offset from the anchor in pixels
+        #[arg(short, long, default_value_t = 0, allow_hyphen_values = true)]
+        x: i32,
+
+        /// Vertical offset from the anchor in pixels
+        #[arg(short, long, default_value_t = 0, allow_hyphen_values = true)]
+        y: i32,
+
+        /// Scale the layer to this width, in pixels or percent of the base width (keeps aspect ratio)
+        #[arg(short, long, conflicts_with = "cover")]
+        width: Option<Length>,
+
+        /// Scale the layer to cover the whole base image (textures, light leaks)
+        #[arg(long, default_value_t = false)]
+        cover: bool,
+    },
+
+    /// Emulate a film stock from its characteristic curves, dye cross-talk and grain
+    Film {
+        /// Built-in stock (portra-400, ektar-100, velvia-50, tri-x-400, hp5) or path to a stock JSON file
+        #[arg(short, long, required_unless_present = "list")]
+        stock: Option<String>,
+
+        /// List the built-in stocks and exit
+        #[arg(long, default_value_t = false)]
+        list: bool,
+
+        /// Skip the stock's grain
+        #[arg(long, default_value_t = false)]
+        no_grain: bool,
+
+        /// Seed for the grain pattern (default: the classic fixed pattern)
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Derive the grain seed from the image content, so every photo gets its own pattern
+        #[arg(long, default_value_t = false, conflicts_with = "seed")]
+        seed_from_content: bool,
+
+        /// Frame index for image sequences: each frame gets a different, reproducible grain pattern
+        #[arg(long, default_value_t = 0)]
+        frame: u64,
+    },
+
     /// Simulate photographic film grain
     Grain {
         /// Grain intensity (0–100)
-        #[arg(short, long, default_value_t = 25)]
+        #[arg(short, long, default_value_t = 25, value_parser = clap::value_parser!(u32).range(0..=100))]
         amount: u32,
 
         /// Particle size: 0 (fine) to 100 (coarse)
-        #[arg(short, long, default_value_t = 25)]
+        #[arg(short, long, default_value_t = 25, value_parser = clap::value_parser!(u32).range(0..=100))]
         size: u32,
 
         /// Texture: 0 (smooth dye clouds) to 100 (sharp silver halide)
-        #[arg(short, long, default_value_t = 50)]
+        #[arg(short, long, default_value_t = 50, value_parser = clap::value_parser!(u32).range(0..=100))]
         roughness: u32,
 
         /// Use identical noise for all channels (B&W film grain)
         #[arg(short = 'M', long, default_value_t = false)]
         monochrome: bool,
+
+        /// Seed for the grain pattern (default: the classic fixed pattern)
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Derive the seed from the image content, so every photo gets its own pattern
+        #[arg(long, default_value_t = false, conflicts_with = "seed")]
+        seed_from_content: bool,
+
+        /// Frame index for image sequences: each frame gets a different, reproducible pattern
+        #[arg(long, default_value_t = 0)]
+        frame: u64,
+
+        /// Grain model: stylized noise, or physically based Poisson grains
+        #[arg(long, value_enum, default_value_t = GrainModel::Noise)]
+        model: GrainModel,
+
+        /// Film stock profile (uses the Poisson model; overrides amount/size/roughness/monochrome)
+        #[arg(short, long, value_enum)]
+        profile: Option<GrainProfile>,
     },
 
     /// Adjust micro-contrast / structure (similar to Lightroom Clarity)
     Structure {
         /// Structure amount: -100 (smooth) to 100 (enhance detail)
-        #[arg(short, long, default_value_t = 25, allow_hyphen_values = true)]
+        #[arg(short, long, default_value_t = 25, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
         amount: i32,
     },
 
+    /// Remove (or add) atmospheric haze using the dark-channel prior
+    Dehaze {
+        /// Dehaze amount: -100 (add haze) to 100 (remove haze)
+        #[arg(short, long, default_value_t = 25, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+        amount: i32,
+    },
+
+    /// Recover highlights and open shadows using an edge-aware base layer
+    ShadowsHighlights {
+        /// Shadows: -100 (deepen) to 100 (open up)
+        #[arg(short, long, default_value_t = 25, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+        shadows: i32,
+
+        /// Highlights: -100 (recover) to 100 (brighten)
+        #[arg(short = 'H', long, default_value_t = -25, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+        highlights: i32,
+    },
+
+    /// Graduated filter: adjust exposure/color/clarity along a linear gradient (e.g. darken a sky)
+    Graduated {
+        /// Gradient direction in degrees: 90 runs top to bottom, 0 left to right
+        #[arg(long, default_value_t = 90.0, allow_hyphen_values = true)]
+        angle: f64,
+
+        /// Where the full effect ends, 0–100 along the gradient
+        #[arg(long, default_value_t = 0.0, value_parser = utils::float_range(0.0, 100.0))]
+        start: f64,
+
+        /// Where the effect has faded out, 0–100 along the gradient
+        #[arg(long, default_value_t = 50.0, value_parser = utils::float_range(0.0, 100.0))]
+        end: f64,
+
+        #[command(flatten)]
+        adjustments: LocalAdjustments,
+    },
+
+    /// Radial filter: adjust exposure/color/clarity inside (or outside) an ellipse
+    Radial {
+        /// Center x, percent of the width
+        #[arg(short, long, default_value_t = 50.0)]
+        x: f64,
+
+        /// Center y, percent of the height
+        #[arg(short, long, default_value_t = 50.0)]
+        y: f64,
+
+        /// Horizontal radius, percent of the longest side
+        #[arg(long, default_value_t = 25.0)]
+        width: f64,
+
+        /// Vertical radius, percent of the longest side
+        #[arg(long, default_value_t = 25.0)]
+        height: f64,
+
+        /// Fade distance beyond the ellipse, percent of the longest side (0–100), as in `--mask radial:feather=`
+        #[arg(short, long, default_value_t = 15, value_parser = clap::value_parser!(u32).range(0..=100))]
+        feather: u32,
+
+        /// Shape: -100 (rectangular) to 100 (elliptical)
+        #[arg(short, long, default_value_t = 100, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+        roundness: i32,
+
+        /// Adjust outside the ellipse instead of inside
+        #[arg(long, default_value_t = false)]
+        invert: bool,
+
+        #[command(flatten)]
+        adjustments: LocalAdjustments,
+    },
+
+    /// Vintage lens imperfections: chromatic aberration, distortion and glow
+    LensFx {
+        /// Lateral chromatic aberration: -100 to 100 (sign flips the red/blue fringe)
+        #[arg(short, long, default_value_t = 30, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+        aberration: i32,
+
+        /// Distortion: -100 (pincushion) to 100 (barrel)
+        #[arg(short, long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+        distortion: i32,
+
+        /// Diffusion-filter glow (0–100)
+        #[arg(short, long, default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..=100))]
+        glow: u32,
+
+        /// Falloff shape shared with vignette: -100 (rectangular) to 100 (circular)
+        #[arg(short, long, default_value_t = 100, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
+        roundness: i32,
+    },
+
+    /// Stamp a text or logo watermark (EXIF placeholders like {artist} and {date} in text)
+    Watermark {
+        /// Watermark text; {artist}, {copyright}, {date}, {year}, {camera}, {make}, {lens}, {iso},
+        /// {focal}, {aperture} and {shutter} are filled from the input's EXIF
+        #[arg(short, long, required_unless_present = "logo", conflicts_with = "logo")]
+        text: Option<String>,
+
+        /// Logo image to place instead of text
+        #[arg(short, long)]
+        logo: Option<PathBuf>,
+
+        /// TTF/OTF font file (defaults to the bundled DejaVu Sans)
+        #[arg(long)]
+        font: Option<PathBuf>,
+
+        /// Mark height in pixels at 1080p (scaled to the image's short side)
+        #[arg(short, long, default_value_t = 36.0)]
+        size: f32,
+
+        /// Text color (hex like #RRGGBB[AA], or black/white/gray)
+        #[arg(short, long, default_value = "white", value_parser = utils::parse_color)]
+        color: Rgba<u8>,
+
+        /// Opacity (0–100)
+        #[arg(long, default_value_t = 50, value_parser = clap::value_parser!(u32).range(0..=100))]
+        opacity: u32,
+
+        /// Where to place the mark
+        #[arg(short, long, value_enum, default_value_t = Gravity::SouthEast)]
+        gravity: Gravity,
+
+        /// Distance from the edges in pixels at 1080p
+        #[arg(short, long, default_value_t = 32.0)]
+        margin: f32,
+
+        /// Rotation in degrees, clockwise like `rotate`
+        #[arg(short, long, default_value_t = 0.0, allow_hyphen_values = true)]
+        rotation: f64,
+
+        /// Repeat the mark across the whole image
+        #[arg(long, default_value_t = false)]
+        tile: bool,
+    },
+
     /// Apply a Lightroom-style vignette effect
     Vignette {
         /// Vignette strength: -100 (darken edges) to 100 (lighten edges)
-        #[arg(short, long, default_value_t = -50, allow_hyphen_values = true)]
+        #[arg(short, long, default_value_t = -50, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
         amount: i32,
 
         /// How far from center the effect starts (0–100)
-        #[arg(short, long, default_value_t = 50)]
+        #[arg(short, long, default_value_t = 50, value_parser = clap::value_parser!(u32).range(0..=100))]
         midpoint: u32,
 
         /// Shape: -100 (rectangular) to 100 (circular)
-        #[arg(short, long, default_value_t = 0, allow_hyphen_values = true)]
+        #[arg(short, long, default_value_t = 0, allow_hyphen_values = true, value_parser = clap::value_parser!(i32).range(-100..=100))]
         roundness: i32,
 
         /// Softness of the transition (0–100)
-        #[arg(short, long, default_value_t = 50)]
+        #[arg(short, long, default_value_t = 50, value_parser = clap::value_parser!(u32).range(0..=100))]
         feather: u32,
     },
 }
 
+#[derive(Subcommand)]
+enum PresetAction {
+    /// Run a preset's pipeline on the input (with --strength to dial the look back)
+    Apply {
+        /// Preset name (a file stem in --dir) or path to a preset file
+        preset: String,
+
+        /// Directory holding the presets
+        #[arg(long, default_value = "presets")]
+        dir: PathBuf,
+    },
+
+    /// Run every preset (or the listed ones) on a downscaled copy of -i and tile the results
+    /// in a grid labelled with each preset's name and description (--strength fades each tile)
+    ContactSheet {
+        /// Preset names (file stems in --dir) or paths to preset files; defaults to all of --dir
+        presets: Vec<String>,
+
+        /// Directory holding the presets
+        #[arg(long, default_value = "presets")]
+        dir: PathBuf,
+
+        /// Longest side of each tile in pixels
+        #[arg(short, long, default_value_t = 400, value_parser = clap::value_parser!(u32).range(1..))]
+        size: u32,
+
+        /// Tiles per row (defaults to a near-square grid)
+        #[arg(short, long)]
+        columns: Option<u32>,
+
+        /// Add the unprocessed image as the first tile
+        #[arg(long, default_value_t = false)]
+        original: bool,
+    },
+
+    /// Check presets against the schema and every step's arguments and ranges; exits with status 1 on problems
+    Validate {
+        /// Preset names (file stems in --dir) or paths to preset files; defaults to all of --dir
+        presets: Vec<String>,
+
+        /// Directory holding the presets
+        #[arg(long, default_value = "presets")]
+        dir: PathBuf,
+    },
+}
+
+impl Command {
+    /// Whether the command turns an image into another one, so it can run
+    /// through [`process`] and as a preset step.
+    fn transforms_image(&self) -> bool {
+        !matches!(
+            self,
+            Command::ShowCurve { .. }
+                | Command::DecodeRaw
+                | Command::Srcset { .. }
+                | Command::Analyze { .. }
+                | Command::Compare { .. }
+                | Command::Preset { action: PresetAction::ContactSheet { .. } | PresetAction::Validate { .. } }
+                | Command::Film { list: true, .. }
+        )
+    }
+
+    /// Why `flag` (`--mask` or `--strength`) can't blend this command's result
+    /// with its input, if it can't: the command has to transform the image and
+    /// keep its size.
+    fn blend_conflict(&self, flag: &str) -> Option<String> {
+        let changes_size = match self {
+            Command::Preset { action: PresetAction::Apply { preset, dir } } => {
+                preset::changes_size(&preset::load(preset, dir))
+            }
+            command => command.changes_size(),
+        };
+        let problem = if !self.transforms_image() {
+            "it doesn't transform an image"
+        } else if changes_size {
+            "it can change the image size"
+        } else {
+            return None;
+        };
+        Some(format!("{flag} can't be used with this command: {problem}"))
+    }
+
+    /// Whether the output can have other dimensions than the input.
+    fn changes_size(&self) -> bool {
+        matches!(
+            self,
+            Command::Resize { .. } | Command::Crop { .. } | Command::Rotate { .. } | Command::Pad { .. } | Command::Frame { .. }
+        )
+    }
+}
+
+/// Exit with a usage error unless the command's result can be blended with
+/// its input under `flag`.
+fn check_blend_target(cli: &Cli, flag: &str) {
+    if let Some(message) = cli.command.blend_conflict(flag) {
+        Cli::command().error(ErrorKind::ArgumentConflict, message).exit();
+    }
+}
+
+/// Load a second image: `-` reads stdin and camera RAW files go through decode-raw.
+fn load_layer(path: &Path) -> DynamicImage {
+    if path == Path::new("-") {
+        load_image(None)
+    } else if commands::decode_raw::is_raw(path) {
+        commands::decode_raw::apply(path)
+    } else {
+        load_image(Some(&path.to_path_buf()))
+    }
+}
+
 fn load_image(path: Option<&PathBuf>) -> DynamicImage {
     match path {
         Some(p) => ImageReader::open(p)
@@ -245,6 +899,14 @@ fn save_image(img: &DynamicImage, path: Option<&PathBuf>) {
 
 fn main() {
     let cli = Cli::parse();
+    if !cli.mask.is_empty() {
+        check_blend_target(&cli, "--mask");
+    }
+    // contact-sheet blends each tile with its own downscaled original
+    let per_tile = matches!(cli.command, Command::Preset { action: PresetAction::ContactSheet { .. } });
+    if cli.strength < 100 && !per_tile {
+        check_blend_target(&cli, "--strength");
+    }
 
     // show-curve doesn't need an input image
     if let Command::ShowCurve { darks, middarks, mids, midhighlights, highlights } = &cli.command {
@@ -261,20 +923,192 @@ fn main() {
         return;
     }
 
+    // analyze writes a JSON report instead of an image
+    if let Command::Analyze { render, colors, max_clipping } = &cli.command {
+        let img = load_image(cli.input.as_ref());
+        let analysis = commands::analyze::apply(&img, *colors, render.as_deref());
+        let json = serde_json::to_string_pretty(&analysis).expect("failed to serialize analysis") + "\n";
+        match cli.output.as_ref() {
+            Some(p) => std::fs::write(p, json).unwrap_or_else(|e| panic!("failed to write {}: {e}", p.display())),
+            None => io::stdout().write_all(json.as_bytes()).expect("failed to write to stdout"),
+        }
+        if let Some(max) = max_clipping
+            && analysis.clipping.highlights > *max
+        {
+            eprintln!("analyze: {}% of pixels clip in the highlights (max {max}%)", analysis.clipping.highlights);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // compare prints metrics and may fail a tolerance check after writing its view
+    if let Command::Compare { other, mode, direction, position, labels, amplify, max_delta_e, strict_size } =
+        &cli.command
+    {
+        assert!(
+            other != Path::new("-") || cli.input.is_some(),
+            "compare reads the other image from stdin, so the input needs -i"
+        );
+        let img = load_image(cli.input.as_ref());
+        let other = load_layer(other);
+        if *strict_size && (other.width(), other.height()) != (img.width(), img.height()) {
+            eprintln!(
+                "compare: {}x{} doesn't match the input's {}x{}",
+                other.width(),
+                other.height(),
+                img.width(),
+                img.height()
+            );
+            std::process::exit(1);
+        }
+        let other = commands::compare::match_size(&img, other);
+        let metrics = commands::compare::metrics(&img, &other);
+        eprintln!("compare: {metrics}");
+        let result = commands::compare::apply(&img, &other, *mode, *direction, *position, labels, *amplify);
+        save_image(&result, cli.output.as_ref());
+        if let Some(max) = max_delta_e
+            && metrics.max_delta_e > *max
+        {
+            eprintln!("compare: max ΔE {:.2} exceeds {max}", metrics.max_delta_e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // preset contact-sheet and validate don't produce a single processed image
+    if let Command::Preset { action } = &cli.command {
+        match action {
+            PresetAction::Apply { .. } => {}
+            PresetAction::ContactSheet { presets, dir, size, columns, original } => {
+                let presets: Vec<preset::Preset> = if presets.is_empty() {
+                    preset::all(dir)
+                } else {
+                    presets.iter().map(|name| preset::load(name, dir)).collect()
+                };
+                // Each tile is blended with the downscaled original, so it has to keep that size
+                if cli.strength < 100
+                    && let Some(resizing) = presets.iter().find(|p| preset::changes_size(p))
+                {
+                    let message = format!("--strength can't be used with preset '{}': it can change the image size", resizing.name);
+                    Cli::command().error(ErrorKind::ArgumentConflict, message).exit();
+                }
+                let img = load_image(cli.input.as_ref());
+                let sheet =
+                    preset::contact_sheet(&img, &presets, *size, *columns, *original, cli.strength, cli.input.as_deref());
+                save_image(&sheet, cli.output.as_ref());
+                return;
+            }
+            PresetAction::Validate { presets, dir } => {
+                let paths: Vec<PathBuf> = if presets.is_empty() {
+                    preset::paths(dir)
+                } else {
+                    presets.iter().map(|name| preset::path(name, dir)).collect()
+                };
+                let mut valid = true;
+                for path in &paths {
+                    let problems = preset::check(path);
+                    if problems.is_empty() {
+                        println!("{}: ok", path.display());
+                    }
+                    for problem in &problems {
+                        eprintln!("{}: {problem}", path.display());
+                    }
+                    valid &= problems.is_empty();
+                }
+                if !valid {
+                    std::process::exit(1);
+                }
+                return;
+            }
+        }
+    }
+
+    // film --list only prints the built-in stocks
+    if let Command::Film { list: true, .. } = &cli.command {
+        commands::film::list_stocks();
+        return;
+    }
+
+    // srcset writes several files from a -o template instead of a single output
+    if let Command::Srcset { widths, format, manifest } = &cli.command {
+        let img = load_image(cli.input.as_ref());
+        let template = cli.output.as_ref().expect("srcset requires -o <template>");
+        let stem = cli
+            .input
+            .as_ref()
+            .and_then(|p| p.file_stem())
+            .map_or("image".into(), |s| s.to_string_lossy());
+        commands::srcset::apply(&img, widths, format, template, &stem, manifest.as_ref());
+        return;
+    }
+
+    if let Command::Composite { layer, .. } = &cli.command {
+        assert!(
+            layer != Path::new("-") || cli.input.is_some(),
+            "composite reads the layer from stdin, so the base image needs -i"
+        );
+    }
+    if let Command::Watermark { logo: Some(logo), .. } = &cli.command {
+        assert!(
+            logo != Path::new("-") || cli.input.is_some(),
+            "watermark reads the logo from stdin, so the input needs -i"
+        );
+    }
+
     let img = load_image(cli.input.as_ref());
+    let output = cli.output.clone();
+    let result = process(cli, img);
+    save_image(&result, output.as_ref());
+}
+
+/// Run an image command on `img`, restricted to `--mask` and faded by
+/// `--strength` when given. Used for the command line itself and for every
+/// step of a preset.
+fn process(cli: Cli, img: DynamicImage) -> DynamicImage {
+    assert!(cli.command.transforms_image(), "this command does not transform an image and can't run as a preset step");
+    let original = (!cli.mask.is_empty() || cli.strength < 100).then(|| img.clone());
+    let mask = (!cli.mask.is_empty()).then(|| mask::build(&cli.mask, &img, cli.mask_invert, cli.mask_feather));
 
     let result = match cli.command {
         Command::Blur { sigma } => commands::blur::apply(img, sigma),
         Command::Unsharpen { sigma, threshold } => commands::unsharpen::apply(img, sigma, threshold),
-        Command::Grayscale => commands::grayscale::apply(img),
-        Command::Resize { output_size } => commands::resize::apply(img, output_size),
+        Command::Grayscale { weights, filter } => {
+            commands::grayscale::apply(img, weights, filter)
+        }
+        Command::Resize { args } => commands::resize::apply(img, &args),
+        Command::Crop { x, y, width, height, aspect, gravity, smart, debug } => match aspect {
+            Some(aspect) if smart => commands::crop::apply_smart(img, aspect, debug.as_deref()),
+            Some(aspect) => commands::crop::apply_aspect(img, aspect, gravity),
+            None => commands::crop::apply(img, x, y, width, height),
+        },
+        Command::Rotate { angle, crop, fill } => commands::rotate::apply(img, angle, crop, fill),
+        Command::Flip { direction } => commands::flip::apply(img, direction),
+        Command::Pad { width, height, color, gravity } => {
+            commands::pad::apply(img, width, height, color, gravity)
+        }
+        Command::Frame { border, top, right, bottom, left, color, aspect, template, label } => match template {
+            Some(template) => commands::frame::apply_template(img, template, color, label.as_deref(), aspect),
+            None => {
+                let sides = [top, right, bottom, left].map(|side| side.unwrap_or(border));
+                commands::frame::apply(img, sides, color.unwrap_or(Rgba([255, 255, 255, 255])), aspect)
+            }
+        },
         Command::Channel { color } => commands::channel::apply(img, color),
+        Command::ChannelMixer { red, green, blue, constant } => {
+            commands::channel_mixer::apply(img, red, green, blue, constant)
+        }
         Command::Curve { darks, middarks, mids, midhighlights, highlights } => {
             commands::curve::apply(img, darks, middarks, mids, midhighlights, highlights)
         }
+        Command::Basic { exposure, contrast, highlights, shadows, whites, blacks } => {
+            commands::basic::apply(img, exposure, contrast, highlights, shadows, whites, blacks)
+        }
         Command::Color { temperature, tint, vibrance, saturation } => {
             commands::color::apply(img, temperature, tint, vibrance, saturation)
         }
+        Command::WhiteBalance { kelvin, tint, sample, auto, adaptation } => {
+            commands::white_balance::apply(img, kelvin, tint, sample, auto, adaptation)
+        }
         Command::ColorGrade {
             shadows_hue, shadows_sat, shadows_lum,
             midtones_hue, midtones_sat, midtones_lum,
@@ -287,15 +1121,73 @@ fn main() {
                 highlights_hue, highlights_sat, highlights_lum,
             )
         }
-        Command::Grain { amount, size, roughness, monochrome } => {
-            commands::grain::apply(img, amount, size, roughness, monochrome)
+        Command::Composite { layer, mode, opacity, gravity, x, y, width, cover } => {
+            let layer = load_layer(&layer);
+            commands::composite::apply(img, layer, mode, opacity, gravity, x, y, width, cover)
+        }
+        Command::Film { stock, no_grain, seed, seed_from_content, frame, .. } => {
+            let stock = commands::film::load_stock(stock.as_deref().expect("film requires --stock"));
+            commands::film::apply(img, &stock, !no_grain, GrainSeed::from_args(seed, seed_from_content), frame)
+        }
+        Command::Hsl { bands } => commands::hsl::apply(img, bands.to_array()),
+        Command::Halation { amount, threshold, radius, hue, mode } => {
+            commands::halation::apply(img, amount, threshold, radius, hue, mode)
+        }
+        Command::Grain {
+            amount, size, roughness, monochrome, seed, seed_from_content, frame, model, profile,
+        } => {
+            let seed = GrainSeed::from_args(seed, seed_from_content);
+            match (profile, model) {
+                (Some(profile), _) => {
+                    let (amount, size, roughness, monochrome) = profile.settings();
+                    commands::grain::apply_poisson(img, amount, size, roughness, monochrome, seed, frame)
+                }
+                (None, GrainModel::Poisson) => {
+                    commands::grain::apply_poisson(img, amount, size, roughness, monochrome, seed, frame)
+                }
+                (None, GrainModel::Noise) => {
+                    commands::grain::apply(img, amount, size, roughness, monochrome, seed, frame)
+                }
+            }
         }
         Command::Structure { amount } => commands::structure::apply(img, amount),
+        Command::Dehaze { amount } => commands::dehaze::apply(img, amount),
+        Command::ShadowsHighlights { shadows, highlights } => {
+            commands::shadows_highlights::apply(img, shadows, highlights)
+        }
+        Command::Graduated { angle, start, end, adjustments } => {
+            commands::graduated::apply(img, angle, start, end, &adjustments)
+        }
+        Command::Radial { x, y, width, height, feather, roundness, invert, adjustments } => {
+            commands::radial::apply(img, x, y, width, height, feather, roundness, invert, &adjustments)
+        }
+        Command::LensFx { aberration, distortion, glow, roundness } => {
+            commands::lens_fx::apply(img, aberration, distortion, glow, roundness)
+        }
+        Command::Watermark { text, logo, font, size, color, opacity, gravity, margin, rotation, tile } => {
+            let expanded;
+            let mark = match (&text, logo) {
+                (Some(text), _) => {
+                    let fields = cli.input.as_deref().map(commands::watermark::exif_fields).unwrap_or_default();
+                    expanded = commands::watermark::expand_placeholders(text, &fields);
+                    Mark::Text { text: &expanded, font: font.as_deref(), color }
+                }
+                (None, Some(logo)) => Mark::Logo(load_layer(&logo)),
+                (None, None) => unreachable!(),
+            };
+            commands::watermark::apply(img, mark, size, gravity, margin, opacity, rotation, tile)
+        }
         Command::Vignette { amount, midpoint, roundness, feather } => {
             commands::vignette::apply(img, amount, midpoint, roundness, feather)
         }
-        Command::ShowCurve { .. } | Command::DecodeRaw => unreachable!(),
+        Command::Preset { action: PresetAction::Apply { preset, dir } } => {
+            preset::run(&preset::load(&preset, &dir), img, cli.input.as_deref()).unwrap_or_else(|e| panic!("{e}"))
+        }
+        _ => unreachable!("process only runs commands that transform an image"),
     };
 
-    save_image(&result, cli.output.as_ref());
+    match original {
+        Some(original) => mask::blend(&original, result, mask.as_deref(), cli.strength as f64 / 100.0),
+        None => result,
+    }
 }
@@ -0,0 +1,234 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use image::{DynamicImage, ImageReader, Rgba, RgbaImage};
+
+use crate::commands::hsl::rgb_to_hsl;
+use crate::commands::vignette::{centered_uv, shape_distance};
+use crate::utils::{box_mean, luminance, relative_sigma, smoothstep};
+
+/// How a mask combines with the masks before it.
+#[derive(Clone, Copy)]
+pub enum MaskOp {
+    /// `+` (default): union
+    Add,
+    /// `-`: remove this region
+    Subtract,
+    /// `*`: keep only the overlap
+    Intersect,
+}
+
+/// A region selection. Values are on 0–100 scales unless noted.
+#[derive(Clone)]
+pub enum MaskShape {
+    /// Luminance between `min` and `max`, with `soft`-wide edges
+    Luma { min: f64, max: f64, soft: f64 },
+    /// Hue within `width`/2 degrees of `hue`, above `min_sat` saturation
+    Hue { hue: f64, width: f64, soft: f64, min_sat: f64 },
+    /// Ramp from 0 at `start` to 1 at `end`, measured along `angle` degrees (0 = left to right, 90 = top to bottom)
+    Linear { angle: f64, start: f64, end: f64 },
+    /// 1 inside the ellipse with radii `width` x `height` around (`x`, `y`), fading out over
+    /// `feather` beyond the longer radius; shape follows `vignette` roundness
+    Radial { x: f64, y: f64, width: f64, height: f64, feather: f64, roundness: i32 },
+    /// Grayscale image, resized to fit
+    Image(PathBuf),
+}
+
+/// One `--mask` argument: `[+|-|*]kind:key=value,...` (or `image:path`).
+#[derive(Clone)]
+pub struct MaskSpec {
+    pub op: MaskOp,
+    pub shape: MaskShape,
+}
+
+impl FromStr for MaskSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (op, rest) = match s.chars().next() {
+            Some('+') => (MaskOp::Add, &s[1..]),
+            Some('-') => (MaskOp::Subtract, &s[1..]),
+            Some('*') => (MaskOp::Intersect, &s[1..]),
+            _ => (MaskOp::Add, s),
+        };
+        let (kind, params) = rest.split_once(':').unwrap_or((rest, ""));
+
+        if kind == "image" {
+            if params.is_empty() {
+                return Err("image mask expects a path: image:mask.png".into());
+            }
+            return Ok(MaskSpec { op, shape: MaskShape::Image(PathBuf::from(params)) });
+        }
+
+        let mut values: Vec<(&str, f64)> = Vec::new();
+        for pair in params.split(',').filter(|p| !p.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("invalid mask parameter '{pair}' (expected key=value)"))?;
+            let value = value
+                .parse::<f64>()
+                .map_err(|_| format!("invalid value for mask parameter '{key}': {value}"))?;
+            values.push((key, value));
+        }
+        let allowed: &[&str] = match kind {
+            "luma" => &["min", "max", "soft"],
+            "hue" => &["hue", "width", "soft", "min-sat"],
+            "linear" => &["angle", "start", "end"],
+            "radial" => &["x", "y", "radius", "width", "height", "feather", "roundness"],
+            _ => return Err(format!("unknown mask kind '{kind}' (expected luma, hue, linear, radial or image)")),
+        };
+        if let Some((key, _)) = values.iter().find(|(key, _)| !allowed.contains(key)) {
+            return Err(format!("unknown {kind} mask parameter '{key}' (expected {})", allowed.join(", ")));
+        }
+        let get = |key: &str, default: f64| values.iter().rev().find(|(k, _)| *k == key).map_or(default, |(_, v)| *v);
+
+        let shape = match kind {
+            "luma" => MaskShape::Luma { min: get("min", 0.0), max: get("max", 100.0), soft: get("soft", 10.0) },
+            "hue" => MaskShape::Hue {
+                hue: get("hue", 0.0),
+                width: get("width", 60.0),
+                soft: get("soft", 20.0),
+                min_sat: get("min-sat", 10.0),
+            },
+            "linear" => MaskShape::Linear { angle: get("angle", 90.0), start: get("start", 0.0), end: get("end", 100.0) },
+            _ => MaskShape::Radial {
+                x: get("x", 50.0),
+                y: get("y", 50.0),
+                width: get("width", get("radius", 30.0)),
+                height: get("height", get("radius", 30.0)),
+                feather: get("feather", 30.0),
+                roundness: get("roundness", 100.0) as i32,
+            },
+        };
+        Ok(MaskSpec { op, shape })
+    }
+}
+
+/// Weight (0–1) of a single mask shape at every pixel of `img`.
+fn shape_weights(shape: &MaskShape, img: &RgbaImage) -> Vec<f64> {
+    let (w, h) = (img.width() as f64, img.height() as f64);
+    let pixel_weight = |x: u32, y: u32, p: &Rgba<u8>| -> f64 {
+        let (r, g, b) = (p[0] as f64 / 255.0, p[1] as f64 / 255.0, p[2] as f64 / 255.0);
+        match *shape {
+            MaskShape::Luma { min, max, soft } => {
+                let (min, max, soft) = (min / 100.0, max / 100.0, soft.max(0.0) / 100.0);
+                let lum = luminance(r, g, b);
+                let lower = if soft > 0.0 { smoothstep(min - soft, min, lum) } else { (lum >= min) as u8 as f64 };
+                let upper = if soft > 0.0 { 1.0 - smoothstep(max, max + soft, lum) } else { (lum <= max) as u8 as f64 };
+                lower * upper
+            }
+            MaskShape::Hue { hue, width, soft, min_sat } => {
+                let (ph, ps, _) = rgb_to_hsl(r, g, b);
+                let diff = (ph - hue).rem_euclid(360.0);
+                let dist = diff.min(360.0 - diff);
+                let half = width / 2.0;
+                let hue_w = 1.0 - smoothstep(half, half + soft.max(1e-6), dist);
+                let min_sat = min_sat / 100.0;
+                hue_w * smoothstep(min_sat * 0.5, min_sat.max(1e-6), ps)
+            }
+            MaskShape::Linear { angle, start, end } => {
+                let (dx, dy) = (angle.to_radians().cos(), angle.to_radians().sin());
+                // Project onto the direction, normalized so the image spans 0..1 along it
+                let extent = dx.abs() * w + dy.abs() * h;
+                let origin = (if dx < 0.0 { w } else { 0.0 }) * dx + (if dy < 0.0 { h } else { 0.0 }) * dy;
+                let t = (x as f64 * dx + y as f64 * dy - origin) / extent;
+                let (start, end) = (start / 100.0, end / 100.0);
+                if start == end {
+                    (t >= start) as u8 as f64
+                } else if start < end {
+                    smoothstep(start, end, t)
+                } else {
+                    1.0 - smoothstep(end, start, t)
+                }
+            }
+            MaskShape::Radial { x: cx, y: cy, width, height, feather, roundness } => {
+                let (uv_x, uv_y) = centered_uv(x as f64, y as f64, w, h);
+                let (c_x, c_y) = centered_uv(cx / 100.0 * w, cy / 100.0 * h, w, h);
+                // Stretch the shorter axis so the ellipse becomes a circle of the longer radius
+                let (rx, ry) = (width.max(1e-6), height.max(1e-6));
+                let radius = rx.max(ry);
+                let dist = shape_distance((uv_x - c_x) * (radius / rx), (uv_y - c_y) * (radius / ry), roundness);
+                let (inner, outer) = (radius / 100.0, (radius + feather.max(1e-6)) / 100.0);
+                1.0 - smoothstep(inner, outer, dist)
+            }
+            MaskShape::Image(_) => unreachable!(),
+        }
+    };
+
+    if let MaskShape::Image(path) = shape {
+        let mask = ImageReader::open(path)
+            .unwrap_or_else(|e| panic!("failed to open mask image {}: {e}", path.display()))
+            .decode()
+            .unwrap_or_else(|e| panic!("failed to decode mask image {}: {e}", path.display()));
+        let mask = mask
+            .resize_exact(img.width(), img.height(), image::imageops::FilterType::Triangle)
+            .to_luma8();
+        return mask.pixels().map(|p| p[0] as f64 / 255.0).collect();
+    }
+    img.enumerate_pixels().map(|(x, y, p)| pixel_weight(x, y, p)).collect()
+}
+
+/// Weights (0–1) of a single shape over `img`, for commands with built-in masks.
+pub(crate) fn shape_mask(shape: &MaskShape, img: &DynamicImage) -> Vec<f64> {
+    shape_weights(shape, &img.to_rgba8())
+}
+
+/// Combine `specs` into one mask over `img`, then optionally invert it and
+/// soften its edges by `feather` pixels (at 1080p, scaled to the image).
+pub(crate) fn build(specs: &[MaskSpec], img: &DynamicImage, invert: bool, feather: f32) -> Vec<f64> {
+    let rgba = img.to_rgba8();
+    let mut mask: Vec<f64> = vec![0.0; (rgba.width() * rgba.height()) as usize];
+    for (i, spec) in specs.iter().enumerate() {
+        let weights = shape_weights(&spec.shape, &rgba);
+        for (m, w) in mask.iter_mut().zip(weights) {
+            // The first mask starts the selection (a leading `-` selects everything else)
+            *m = match (i == 0, spec.op) {
+                (true, MaskOp::Subtract) => 1.0 - w,
+                (true, _) => w,
+                (false, MaskOp::Add) => *m + w - *m * w,
+                (false, MaskOp::Subtract) => *m * (1.0 - w),
+                (false, MaskOp::Intersect) => *m * w,
+            };
+        }
+    }
+
+    if feather > 0.0 {
+        // Two box passes approximate a Gaussian of the same spread
+        let sigma = relative_sigma(rgba.width(), rgba.height(), feather, 0.5);
+        let r = ((sigma * 3f32.sqrt()) as usize).max(1);
+        let (w, h) = (rgba.width() as usize, rgba.height() as usize);
+        mask = box_mean(&box_mean(&mask, w, h, r), w, h, r);
+    }
+    if invert {
+        mask.iter_mut().for_each(|m| *m = 1.0 - *m);
+    }
+    mask
+}
+
+/// Blend `result` over `original` through `mask` (1 = fully processed, no
+/// mask = everywhere), scaled by `strength` (0–1).
+pub(crate) fn blend(original: &DynamicImage, result: DynamicImage, mask: Option<&[f64]>, strength: f64) -> DynamicImage {
+    assert!(
+        original.width() == result.width() && original.height() == result.height(),
+        "--mask and --strength need a command that keeps the image size ({}x{} became {}x{})",
+        original.width(),
+        original.height(),
+        result.width(),
+        result.height()
+    );
+    let has_alpha = result.color().has_alpha();
+    let base = original.to_rgba8();
+    let mut out = result.to_rgba8();
+    for (i, (pixel, orig)) in out.pixels_mut().zip(base.pixels()).enumerate() {
+        let m = mask.map_or(1.0, |mask| mask[i]) * strength;
+        for c in 0..4 {
+            let v = orig[c] as f64 + (pixel[c] as f64 - orig[c] as f64) * m;
+            pixel[c] = v.round().clamp(0.0, 255.0) as u8;
+        }
+    }
+    if has_alpha {
+        DynamicImage::ImageRgba8(out)
+    } else {
+        DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(out).to_rgb8())
+    }
+}
@@ -0,0 +1,262 @@
+use std::path::{Path, PathBuf};
+
+use clap::{CommandFactory, Parser};
+use image::DynamicImage;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::commands::contact_sheet::{self, Tile};
+use crate::{Cli, mask, process};
+
+/// Newest preset schema version this build reads.
+pub const VERSION: u32 = 1;
+
+/// A saved pipeline from `presets/*.json`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Preset {
+    /// Schema version; presets written before versioning count as version 1
+    #[serde(default = "first_version")]
+    pub version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Parent preset (name or path) whose pipeline this one starts from
+    #[serde(default)]
+    pub extends: Option<String>,
+    #[serde(default)]
+    pub pipeline: Vec<Step>,
+}
+
+/// One command of a pipeline, with only its non-default arguments.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Step {
+    pub command: String,
+    #[serde(default)]
+    pub args: Map<String, Value>,
+}
+
+fn first_version() -> u32 {
+    1
+}
+
+/// Path of a preset given by name (a file stem in `dir`) or as a path to a JSON file.
+pub fn path(preset: &str, dir: &Path) -> PathBuf {
+    if preset.ends_with(".json") || preset.contains(std::path::MAIN_SEPARATOR) {
+        PathBuf::from(preset)
+    } else {
+        dir.join(format!("{preset}.json"))
+    }
+}
+
+/// Load a preset by name or path, resolving `extends`.
+pub fn load(preset: &str, dir: &Path) -> Preset {
+    read(&path(preset, dir), &mut Vec::new()).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Every preset file in `dir`, sorted by name.
+pub fn paths(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("failed to read preset directory {}: {e}", dir.display()))
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Every preset in `dir`, sorted by file name.
+pub fn all(dir: &Path) -> Vec<Preset> {
+    paths(dir).iter().map(|path| load(&path.to_string_lossy(), dir)).collect()
+}
+
+/// Parse the preset at `path` and merge it onto its parent. `chain` holds the
+/// files already visited, to catch `extends` cycles. A parent given by name is
+/// looked up next to the file that extends it.
+fn read(path: &Path, chain: &mut Vec<PathBuf>) -> Result<Preset, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| format!("failed to read preset {}: {e}", path.display()))?;
+    let mut preset: Preset =
+        serde_json::from_str(&json).map_err(|e| format!("invalid preset {}: {e}", path.display()))?;
+    if preset.version > VERSION {
+        return Err(format!(
+            "preset {} has version {}, but this imagecli only reads up to version {VERSION}",
+            path.display(),
+            preset.version
+        ));
+    }
+
+    chain.push(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+    if let Some(parent) = preset.extends.take() {
+        let parent_path = self::path(&parent, path.parent().unwrap_or(Path::new(".")));
+        if chain.contains(&parent_path.canonicalize().unwrap_or_else(|_| parent_path.clone())) {
+            return Err(format!("preset {} extends itself through {parent}", path.display()));
+        }
+        let parent = read(&parent_path, chain)?;
+        preset.pipeline = inherit(parent.pipeline, preset.pipeline);
+        if preset.description.is_empty() {
+            preset.description = parent.description;
+        }
+    }
+    Ok(preset)
+}
+
+/// The parent pipeline with each child step merged into the first unmerged
+/// parent step of the same command (child arguments win, and `null` resets one
+/// to its default). Child steps without a match are appended.
+fn inherit(mut pipeline: Vec<Step>, overrides: Vec<Step>) -> Vec<Step> {
+    let mut merged = vec![false; pipeline.len()];
+    for step in overrides {
+        match (0..pipeline.len()).find(|&i| !merged[i] && pipeline[i].command == step.command) {
+            Some(i) => {
+                pipeline[i].args.extend(step.args);
+                merged[i] = true;
+            }
+            None => {
+                pipeline.push(step);
+                merged.push(true);
+            }
+        }
+    }
+    pipeline
+}
+
+/// Problems with the preset at `path`: unreadable or malformed JSON, an
+/// unsupported version, a broken `extends`, or steps that fail [`validate`].
+pub fn check(path: &Path) -> Vec<String> {
+    match read(path, &mut Vec::new()) {
+        Ok(preset) => validate(&preset),
+        Err(e) => vec![e],
+    }
+}
+
+/// Check every step against the command-line definition: unknown commands and
+/// arguments, values of the wrong type or out of range, conflicting arguments,
+/// commands that don't transform an image, and `mask` or `strength` on
+/// commands they can't blend.
+pub fn validate(preset: &Preset) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (i, step) in preset.pipeline.iter().enumerate() {
+        let problem = match Cli::try_parse_from(step_argv(step)) {
+            Ok(cli) => match step_problem(&cli) {
+                Some(problem) => problem,
+                None => continue,
+            },
+            Err(e) => {
+                // The error line plus clap's "did you mean" tips, without usage and help hints
+                let message = e.render().to_string();
+                let mut lines = message.lines().map(str::trim);
+                let error = lines.next().unwrap_or_default().trim_start_matches("error: ");
+                let tips = lines.filter(|line| line.starts_with("tip:"));
+                std::iter::once(error).chain(tips).collect::<Vec<_>>().join("; ")
+            }
+        };
+        problems.push(format!("step {} ({}): {problem}", i + 1, step.command));
+    }
+    problems
+}
+
+/// Why a parsed step can't run in a pipeline, if it can't.
+fn step_problem(cli: &Cli) -> Option<String> {
+    if !cli.command.transforms_image() {
+        return Some("this command does not transform an image".into());
+    }
+    if !cli.mask.is_empty() {
+        return cli.command.blend_conflict("--mask");
+    }
+    if cli.strength < 100 {
+        return cli.command.blend_conflict("--strength");
+    }
+    None
+}
+
+/// Whether any step can change the image size (see `Command::changes_size`).
+pub fn changes_size(preset: &Preset) -> bool {
+    preset
+        .pipeline
+        .iter()
+        .any(|step| Cli::try_parse_from(step_argv(step)).is_ok_and(|cli| cli.command.changes_size()))
+}
+
+/// Command-line arguments for a step: `args` keys become `--key=value` flags
+/// (positional arguments take the bare value), `true` becomes `--key`, and
+/// arrays are joined with commas.
+fn step_argv(step: &Step) -> Vec<String> {
+    let cli = Cli::command();
+    let subcommand = cli.find_subcommand(&step.command);
+    let is_positional = |key: &str| {
+        subcommand
+            .and_then(|sub| sub.get_arguments().find(|arg| arg.get_id() == key.replace('-', "_").as_str()))
+            .is_some_and(|arg| arg.is_positional())
+    };
+
+    let mut argv = vec!["imagecli".to_string(), step.command.clone()];
+    for (key, value) in &step.args {
+        let value = match value {
+            Value::Null | Value::Bool(false) => continue,
+            Value::Bool(true) => {
+                argv.push(format!("--{key}"));
+                continue;
+            }
+            Value::String(s) => s.clone(),
+            Value::Array(items) => items
+                .iter()
+                .map(|item| item.as_str().map_or_else(|| item.to_string(), str::to_string))
+                .collect::<Vec<_>>()
+                .join(","),
+            other => other.to_string(),
+        };
+        if is_positional(key) {
+            argv.push(value);
+        } else {
+            argv.push(format!("--{key}={value}"));
+        }
+    }
+    argv
+}
+
+/// Run every step of `preset` on `img`. `input` is the original file, for
+/// steps that read its metadata (e.g. watermark placeholders). Fails on a step
+/// that doesn't parse or can't run in a pipeline (see [`validate`]).
+pub fn run(preset: &Preset, img: DynamicImage, input: Option<&Path>) -> Result<DynamicImage, String> {
+    preset.pipeline.iter().try_fold(img, |img, step| {
+        let error = |e: &dyn std::fmt::Display| format!("preset '{}', step '{}': {e}", preset.name, step.command);
+        let mut cli = Cli::try_parse_from(step_argv(step)).map_err(|e| error(&e))?;
+        if let Some(problem) = step_problem(&cli) {
+            return Err(error(&problem));
+        }
+        cli.input = input.map(Path::to_path_buf);
+        Ok(process(cli, img))
+    })
+}
+
+/// Run each preset on a copy of `img` downscaled to `size`, blended back with
+/// that copy at `strength` (0–100), and tile the results, optionally after the
+/// unprocessed copy.
+pub fn contact_sheet(
+    img: &DynamicImage,
+    presets: &[Preset],
+    size: u32,
+    columns: Option<u32>,
+    original: bool,
+    strength: u32,
+    input: Option<&Path>,
+) -> DynamicImage {
+    let small = img.thumbnail(size, size);
+    let mut tiles = Vec::new();
+    if original {
+        tiles.push(Tile { image: small.clone(), name: "Original".into(), description: String::new() });
+    }
+    for preset in presets {
+        let mut image = run(preset, small.clone(), input).unwrap_or_else(|e| panic!("{e}"));
+        if strength < 100 {
+            image = mask::blend(&small, image, None, strength as f64 / 100.0);
+        }
+        tiles.push(Tile {
+            image,
+            name: preset.name.clone(),
+            description: preset.description.clone(),
+        });
+    }
+    contact_sheet::apply(&tiles, size, columns)
+}
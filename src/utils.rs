@@ -1,4 +1,246 @@
+use std::str::FromStr;
+
+use clap::ValueEnum;
+use image::{DynamicImage, RgbImage, Rgba};
+
 pub(crate) fn smoothstep(edge0: f64, edge1: f64, x: f64) -> f64 {
     let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
     t * t * (3.0 - 2.0 * t)
 }
+
+/// The processed `rgb` as the result for `original`: RGBA carrying the
+/// original's alpha when it has one, RGB otherwise.
+pub(crate) fn keep_alpha(original: &DynamicImage, rgb: RgbImage) -> DynamicImage {
+    if !original.color().has_alpha() {
+        return DynamicImage::ImageRgb8(rgb);
+    }
+    let mut rgba = original.to_rgba8();
+    for (out, p) in rgba.pixels_mut().zip(rgb.pixels()) {
+        out.0[..3].copy_from_slice(&p.0);
+    }
+    DynamicImage::ImageRgba8(rgba)
+}
+
+/// Rec. 709 luminance.
+pub(crate) fn luminance(r: f64, g: f64, b: f64) -> f64 {
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// Push a 0–1 value toward white (amount > 0) or black (amount < 0), scaled by `weight`.
+pub(crate) fn tone_push(v: f64, amount: f64, weight: f64) -> f64 {
+    if amount > 0.0 {
+        v + (1.0 - v) * amount * weight * 0.5
+    } else {
+        v + v * amount * weight * 0.5
+    }
+}
+
+/// sRGB transfer function: encoded 0–1 value to linear light.
+pub(crate) fn srgb_to_linear(v: f64) -> f64 {
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Inverse sRGB transfer function: linear light to encoded 0–1 value.
+pub(crate) fn linear_to_srgb(v: f64) -> f64 {
+    if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Scale a filter radius to the image dimensions (relative to a 1080p baseline).
+pub(crate) fn relative_sigma(width: u32, height: u32, base: f32, min: f32) -> f32 {
+    let scale = width.min(height) as f32 / 1080.0;
+    (base * scale).max(min)
+}
+
+/// Mean over a (2r+1)x(2r+1) window, computed with a summed-area table.
+/// Windows are clipped at the image border.
+pub(crate) fn box_mean(src: &[f64], w: usize, h: usize, r: usize) -> Vec<f64> {
+    let stride = w + 1;
+    let mut sat = vec![0.0; stride * (h + 1)];
+    for y in 0..h {
+        let mut row = 0.0;
+        for x in 0..w {
+            row += src[y * w + x];
+            sat[(y + 1) * stride + x + 1] = sat[y * stride + x + 1] + row;
+        }
+    }
+
+    let mut out = vec![0.0; w * h];
+    for y in 0..h {
+        let y0 = y.saturating_sub(r);
+        let y1 = (y + r + 1).min(h);
+        for x in 0..w {
+            let x0 = x.saturating_sub(r);
+            let x1 = (x + r + 1).min(w);
+            let sum = sat[y1 * stride + x1] - sat[y0 * stride + x1] - sat[y1 * stride + x0]
+                + sat[y0 * stride + x0];
+            out[y * w + x] = sum / ((x1 - x0) * (y1 - y0)) as f64;
+        }
+    }
+    out
+}
+
+/// Edge-preserving guided filter (He et al.): smooths `src` while following
+/// the edges of `guide`. `eps` sets how strong an edge must be to survive.
+pub(crate) fn guided_filter(guide: &[f64], src: &[f64], w: usize, h: usize, r: usize, eps: f64) -> Vec<f64> {
+    let mean_i = box_mean(guide, w, h, r);
+    let mean_p = box_mean(src, w, h, r);
+    let ii: Vec<f64> = guide.iter().map(|v| v * v).collect();
+    let ip: Vec<f64> = guide.iter().zip(src).map(|(a, b)| a * b).collect();
+    let corr_i = box_mean(&ii, w, h, r);
+    let corr_ip = box_mean(&ip, w, h, r);
+
+    let mut a = vec![0.0; w * h];
+    let mut b = vec![0.0; w * h];
+    for i in 0..w * h {
+        let var_i = corr_i[i] - mean_i[i] * mean_i[i];
+        let cov_ip = corr_ip[i] - mean_i[i] * mean_p[i];
+        a[i] = cov_ip / (var_i + eps);
+        b[i] = mean_p[i] - a[i] * mean_i[i];
+    }
+
+    let mean_a = box_mean(&a, w, h, r);
+    let mean_b = box_mean(&b, w, h, r);
+    (0..w * h).map(|i| mean_a[i] * guide[i] + mean_b[i]).collect()
+}
+
+/// Where to anchor a box inside a larger area.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Gravity {
+    Center,
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl Gravity {
+    /// Top-left offset of an `inner` box anchored inside an `outer` box.
+    pub(crate) fn offset(self, outer: (u32, u32), inner: (u32, u32)) -> (u32, u32) {
+        let free_w = outer.0.saturating_sub(inner.0);
+        let free_h = outer.1.saturating_sub(inner.1);
+        let x = match self {
+            Gravity::West | Gravity::NorthWest | Gravity::SouthWest => 0,
+            Gravity::East | Gravity::NorthEast | Gravity::SouthEast => free_w,
+            _ => free_w / 2,
+        };
+        let y = match self {
+            Gravity::North | Gravity::NorthEast | Gravity::NorthWest => 0,
+            Gravity::South | Gravity::SouthEast | Gravity::SouthWest => free_h,
+            _ => free_h / 2,
+        };
+        (x, y)
+    }
+}
+
+/// A length in pixels (`120`) or as a percentage of a reference size (`25%`).
+#[derive(Clone, Copy)]
+pub enum Length {
+    Pixels(u32),
+    Percent(f64),
+}
+
+impl Length {
+    pub(crate) fn resolve(self, total: u32) -> u32 {
+        match self {
+            Length::Pixels(px) => px,
+            Length::Percent(pct) => (total as f64 * pct / 100.0).round() as u32,
+        }
+    }
+}
+
+impl FromStr for Length {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_suffix('%') {
+            Some(pct) => pct
+                .parse::<f64>()
+                .ok()
+                .filter(|v| *v >= 0.0)
+                .map(Length::Percent)
+                .ok_or_else(|| format!("invalid percentage: {s}")),
+            None => s.parse::<u32>().map(Length::Pixels).map_err(|_| format!("invalid length: {s}")),
+        }
+    }
+}
+
+/// An aspect ratio written as `W:H` (e.g. `16:9`) or a decimal (`1.5`).
+#[derive(Clone, Copy)]
+pub struct Aspect(pub f64);
+
+impl FromStr for Aspect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ratio = match s.split_once(':') {
+            Some((w, h)) => match (w.parse::<f64>(), h.parse::<f64>()) {
+                (Ok(w), Ok(h)) if h > 0.0 => w / h,
+                _ => return Err(format!("invalid aspect ratio: {s}")),
+            },
+            None => s.parse::<f64>().map_err(|_| format!("invalid aspect ratio: {s}"))?,
+        };
+        if ratio > 0.0 { Ok(Aspect(ratio)) } else { Err(format!("invalid aspect ratio: {s}")) }
+    }
+}
+
+/// Parse a color as `#RRGGBB`, `#RRGGBBAA` (the `#` is optional) or a common name.
+pub(crate) fn parse_color(s: &str) -> Result<Rgba<u8>, String> {
+    match s.to_ascii_lowercase().as_str() {
+        "black" => return Ok(Rgba([0, 0, 0, 255])),
+        "white" => return Ok(Rgba([255, 255, 255, 255])),
+        "gray" | "grey" => return Ok(Rgba([128, 128, 128, 255])),
+        "transparent" => return Ok(Rgba([0, 0, 0, 0])),
+        _ => {}
+    }
+    let hex = s.trim_start_matches('#');
+    if !hex.is_ascii() {
+        return Err(format!("invalid color: {s}"));
+    }
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| format!("invalid color: {s}"));
+    match hex.len() {
+        6 => Ok(Rgba([byte(0)?, byte(2)?, byte(4)?, 255])),
+        8 => Ok(Rgba([byte(0)?, byte(2)?, byte(4)?, byte(6)?])),
+        _ => Err(format!("invalid color: {s}")),
+    }
+}
+
+/// Value parser for a float argument limited to `min..=max` (clap's ranged
+/// parsers only cover integers).
+pub(crate) fn float_range<T>(min: T, max: T) -> impl Fn(&str) -> Result<T, String> + Clone + Send + Sync + 'static
+where
+    T: FromStr + PartialOrd + std::fmt::Display + Copy + Send + Sync + 'static,
+{
+    move |s: &str| {
+        let value: T = s.parse().map_err(|_| format!("invalid number: {s}"))?;
+        if value >= min && value <= max { Ok(value) } else { Err(format!("{s} is not in {min}..={max}")) }
+    }
+}
+
+/// Value parser for exactly `N` comma-separated integers, each within `min..=max`.
+pub(crate) fn int_tuple<const N: usize>(min: i32, max: i32) -> impl Fn(&str) -> Result<[i32; N], String> + Clone + Send + Sync + 'static {
+    move |s: &str| {
+        let values = s
+            .split(',')
+            .map(|v| v.trim().parse::<i32>().map_err(|_| format!("invalid number: {v}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        let values: [i32; N] = values
+            .try_into()
+            .map_err(|v: Vec<i32>| format!("expected {N} comma-separated values, got {}", v.len()))?;
+        match values.iter().find(|v| !(min..=max).contains(*v)) {
+            Some(v) => Err(format!("{v} is not in {min}..={max}")),
+            None => Ok(values),
+        }
+    }
+}
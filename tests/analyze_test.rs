@@ -0,0 +1,81 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_analyze(input: &str, args: &[&str]) -> (std::process::Output, std::time::Duration) {
+    let mut cmd_args = vec!["-i", input, "analyze"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let output = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .output()
+        .expect("failed to execute imagecli");
+    (output, start.elapsed())
+}
+
+#[test]
+fn analyze_report() {
+    let (output, elapsed) = run_analyze("lena.png", &[]);
+    println!("analyze latency: {elapsed:?}");
+    assert!(output.status.success(), "imagecli analyze failed");
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["width"], 512);
+    assert_eq!(json["height"], 512);
+    for channel in ["red", "green", "blue", "luminance"] {
+        let stats = &json["channels"][channel];
+        let histogram = stats["histogram"].as_array().unwrap();
+        assert_eq!(histogram.len(), 256);
+        assert_eq!(histogram.iter().map(|n| n.as_u64().unwrap()).sum::<u64>(), 512 * 512);
+
+        let p = &stats["percentiles"];
+        let ordered = [&stats["min"], &p["p1"], &p["p25"], &stats["median"], &p["p75"], &p["p99"], &stats["max"]];
+        assert!(
+            ordered.windows(2).all(|w| w[0].as_u64() <= w[1].as_u64()),
+            "{channel} percentiles are not ordered"
+        );
+    }
+
+    // Lena is a warm, mostly red image
+    assert!(json["channels"]["red"]["mean"].as_f64() > json["channels"]["blue"]["mean"].as_f64());
+    assert!(json["color_temperature"].as_u64().unwrap() < 5000);
+
+    let colors = json["dominant_colors"].as_array().unwrap();
+    assert_eq!(colors.len(), 5);
+    let share: f64 = colors.iter().map(|c| c["share"].as_f64().unwrap()).sum();
+    assert!((share - 1.0).abs() < 0.01, "dominant color shares sum to {share}");
+}
+
+#[test]
+fn analyze_render_histogram() {
+    let fixture = "tests/fixtures/analyze/histogram.png";
+    let output = "tests/fixtures/analyze/histogram_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let (result, elapsed) = run_analyze("lena.png", &["--render", output]);
+    println!("analyze render latency: {elapsed:?}");
+    assert!(result.status.success(), "imagecli analyze --render failed");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "analyze histogram plot differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn analyze_max_clipping() {
+    // 0.04% of lena's pixels reach 255 in the red channel
+    let (output, _) = run_analyze("lena.png", &["--max-clipping", "1"]);
+    assert!(output.status.success(), "1% clipping limit should pass");
+
+    let (output, _) = run_analyze("lena.png", &["--max-clipping", "0"]);
+    assert_eq!(output.status.code(), Some(1), "0% clipping limit should fail");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("clip in the highlights"));
+    // The report is still written
+    assert!(serde_json::from_slice::<serde_json::Value>(&output.stdout).is_ok());
+}
@@ -0,0 +1,76 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_basic(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "basic"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli basic {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn basic_plus_one_ev() {
+    let fixture = "tests/fixtures/basic/plus_one_ev.png";
+    let output = "tests/fixtures/basic/plus_one_ev_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_basic("lena.png", output, &["--exposure", "1"]);
+    println!("basic +1 EV latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "basic +1 EV output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn basic_contrast_clip_points() {
+    let fixture = "tests/fixtures/basic/contrast_clip_points.png";
+    let output = "tests/fixtures/basic/contrast_clip_points_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_basic(
+        "lena.png",
+        output,
+        &["--contrast", "40", "--whites", "20", "--blacks=-30"],
+    );
+    println!("basic contrast clip points latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "basic contrast clip points output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn basic_highlights_shadows() {
+    let fixture = "tests/fixtures/basic/highlights_shadows.png";
+    let output = "tests/fixtures/basic/highlights_shadows_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_basic(
+        "lena.png",
+        output,
+        &["--exposure=-0.5", "--highlights=-60", "--shadows", "50"],
+    );
+    println!("basic highlights/shadows latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "basic highlights/shadows output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
@@ -2,14 +2,8 @@ use std::path::Path;
 use std::process::Command;
 use std::time::Instant;
 
-fn imagecli_bin() -> std::path::PathBuf {
-    let mut path = std::env::current_exe().unwrap();
-    // integration test binaries live in target/debug/deps/
-    path.pop(); // remove test binary name
-    path.pop(); // remove "deps"
-    path.push("imagecli");
-    path
-}
+mod common;
+use common::{imagecli_bin, images_match};
 
 fn run_blur(input: &str, output: &str, sigma: f32) -> std::time::Duration {
     let start = Instant::now();
@@ -22,17 +16,6 @@ fn run_blur(input: &str, output: &str, sigma: f32) -> std::time::Duration {
     elapsed
 }
 
-fn images_are_identical(path_a: &str, path_b: &str) -> bool {
-    let a = image::open(path_a).expect("failed to open image A").to_rgb8();
-    let b = image::open(path_b).expect("failed to open image B").to_rgb8();
-
-    if a.dimensions() != b.dimensions() {
-        return false;
-    }
-
-    a.pixels().zip(b.pixels()).all(|(pa, pb)| pa == pb)
-}
-
 #[test]
 fn blur_sigma_1() {
     let fixture = "tests/fixtures/blur/sigma_1.0.png";
@@ -43,7 +26,7 @@ fn blur_sigma_1() {
     println!("blur sigma=1.0 latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "blur sigma=1.0 output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -59,7 +42,7 @@ fn blur_sigma_2() {
     println!("blur sigma=2.0 latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "blur sigma=2.0 output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -75,7 +58,7 @@ fn blur_sigma_5() {
     println!("blur sigma=5.0 latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "blur sigma=5.0 output differs from fixture"
     );
     std::fs::remove_file(output).ok();
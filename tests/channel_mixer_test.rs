@@ -0,0 +1,76 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_channel_mixer(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "channel-mixer"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli channel-mixer {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn channel_mixer_swap_red_blue() {
+    let fixture = "tests/fixtures/channel-mixer/swap_red_blue.png";
+    let output = "tests/fixtures/channel-mixer/swap_red_blue_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_channel_mixer("lena.png", output, &["--red=0,0,100", "--blue=100,0,0"]);
+    println!("channel-mixer swap red/blue latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "channel-mixer swap red/blue output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn channel_mixer_infrared() {
+    let fixture = "tests/fixtures/channel-mixer/infrared.png";
+    let output = "tests/fixtures/channel-mixer/infrared_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_channel_mixer(
+        "lena.png",
+        output,
+        &["--red=-50,200,-50", "--green=0,100,0", "--blue=100,0,0"],
+    );
+    println!("channel-mixer infrared-style latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "channel-mixer infrared-style output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn channel_mixer_constant_offset() {
+    let fixture = "tests/fixtures/channel-mixer/constant_offset.png";
+    let output = "tests/fixtures/channel-mixer/constant_offset_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_channel_mixer(
+        "lena.png",
+        output,
+        &["--green=10,80,10", "--constant=5,0,-10"],
+    );
+    println!("channel-mixer constant offset latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "channel-mixer constant offset output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
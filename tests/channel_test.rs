@@ -2,13 +2,8 @@ use std::path::Path;
 use std::process::Command;
 use std::time::Instant;
 
-fn imagecli_bin() -> std::path::PathBuf {
-    let mut path = std::env::current_exe().unwrap();
-    path.pop();
-    path.pop();
-    path.push("imagecli");
-    path
-}
+mod common;
+use common::{imagecli_bin, images_match};
 
 fn run_channel(input: &str, output: &str, channel: &str) -> std::time::Duration {
     let start = Instant::now();
@@ -21,16 +16,6 @@ fn run_channel(input: &str, output: &str, channel: &str) -> std::time::Duration
     elapsed
 }
 
-fn images_are_identical(path_a: &str, path_b: &str) -> bool {
-    let a = image::open(path_a).expect("failed to open image A");
-    let b = image::open(path_b).expect("failed to open image B");
-
-    let a = a.as_bytes();
-    let b = b.as_bytes();
-
-    a == b
-}
-
 #[test]
 fn channel_red() {
     let fixture = "tests/fixtures/channel/red.png";
@@ -41,7 +26,7 @@ fn channel_red() {
     println!("channel red latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "channel red output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -57,7 +42,7 @@ fn channel_green() {
     println!("channel green latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "channel green output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -73,7 +58,7 @@ fn channel_blue() {
     println!("channel blue latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "channel blue output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -2,13 +2,8 @@ use std::path::Path;
 use std::process::Command;
 use std::time::Instant;
 
-fn imagecli_bin() -> std::path::PathBuf {
-    let mut path = std::env::current_exe().unwrap();
-    path.pop();
-    path.pop();
-    path.push("imagecli");
-    path
-}
+mod common;
+use common::{imagecli_bin, images_match};
 
 fn run_color_grade(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
     let mut cmd_args = vec!["-i", input, "-o", output, "color-grade"];
@@ -24,17 +19,6 @@ fn run_color_grade(input: &str, output: &str, args: &[&str]) -> std::time::Durat
     elapsed
 }
 
-fn images_are_identical(path_a: &str, path_b: &str) -> bool {
-    let a = image::open(path_a).expect("failed to open image A").to_rgb8();
-    let b = image::open(path_b).expect("failed to open image B").to_rgb8();
-
-    if a.dimensions() != b.dimensions() {
-        return false;
-    }
-
-    a.pixels().zip(b.pixels()).all(|(pa, pb)| pa == pb)
-}
-
 #[test]
 fn color_grade_warm_shadows() {
     let fixture = "tests/fixtures/color-grade/warm_shadows.png";
@@ -45,7 +29,7 @@ fn color_grade_warm_shadows() {
     println!("color-grade warm shadows latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "color-grade warm shadows output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -65,7 +49,7 @@ fn color_grade_teal_orange() {
     println!("color-grade teal/orange latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "color-grade teal/orange output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -89,7 +73,7 @@ fn color_grade_full() {
     println!("color-grade full (all ranges) latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "color-grade full output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -2,13 +2,8 @@ use std::path::Path;
 use std::process::Command;
 use std::time::Instant;
 
-fn imagecli_bin() -> std::path::PathBuf {
-    let mut path = std::env::current_exe().unwrap();
-    path.pop();
-    path.pop();
-    path.push("imagecli");
-    path
-}
+mod common;
+use common::{imagecli_bin, images_match};
 
 fn run_color(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
     let mut cmd_args = vec!["-i", input, "-o", output, "color"];
@@ -24,17 +19,6 @@ fn run_color(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
     elapsed
 }
 
-fn images_are_identical(path_a: &str, path_b: &str) -> bool {
-    let a = image::open(path_a).expect("failed to open image A").to_rgb8();
-    let b = image::open(path_b).expect("failed to open image B").to_rgb8();
-
-    if a.dimensions() != b.dimensions() {
-        return false;
-    }
-
-    a.pixels().zip(b.pixels()).all(|(pa, pb)| pa == pb)
-}
-
 #[test]
 fn color_warm() {
     let fixture = "tests/fixtures/color/warm.png";
@@ -45,7 +29,7 @@ fn color_warm() {
     println!("color warm (temperature=40) latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "color warm output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -61,7 +45,7 @@ fn color_cool_desaturated() {
     println!("color cool+desat (saturation=-30 temperature=-20) latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "color cool+desat output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -77,7 +61,7 @@ fn color_vibrance_tint() {
     println!("color vibrance+tint (vibrance=60 tint=25) latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "color vibrance+tint output differs from fixture"
     );
     std::fs::remove_file(output).ok();
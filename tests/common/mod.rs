@@ -0,0 +1,24 @@
+use std::process::{Command, Stdio};
+
+/// Path of the imagecli binary built alongside the tests.
+pub fn imagecli_bin() -> std::path::PathBuf {
+    let mut path = std::env::current_exe().unwrap();
+    path.pop();
+    path.pop();
+    path.push("imagecli");
+    path
+}
+
+/// Whether `path_b` has the size of `path_a` and every pixel within
+/// `max_delta_e` (CIEDE2000) of it, checked with `imagecli compare
+/// --strict-size`. A tolerance of 0 means identical.
+pub fn images_match(path_a: &str, path_b: &str, max_delta_e: f64) -> bool {
+    let tolerance = max_delta_e.to_string();
+    let status = Command::new(imagecli_bin())
+        .args(["-i", path_a, "compare", path_b, "--strict-size", "--max-delta-e", &tolerance])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .expect("failed to execute imagecli");
+    status.success()
+}
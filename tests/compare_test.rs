@@ -0,0 +1,80 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+const AFTER: &str = "tests/fixtures/film/velvia_50_no_grain.png";
+
+fn run_compare(input: &str, output: &str, args: &[&str]) -> (String, std::time::Duration) {
+    let mut cmd_args = vec!["-i", input, "-o", output, "compare"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let result = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .output()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(result.status.success(), "imagecli compare {args:?} failed");
+    (String::from_utf8_lossy(&result.stderr).into_owned(), elapsed)
+}
+
+#[test]
+fn compare_split_diagonal() {
+    let fixture = "tests/fixtures/compare/split_diagonal.png";
+    let output = "tests/fixtures/compare/split_diagonal_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let (metrics, elapsed) = run_compare("lena.png", output, &[AFTER, "-d", "diagonal", "-p", "40"]);
+    println!("compare split diagonal latency: {elapsed:?}");
+    assert!(metrics.contains("PSNR") && metrics.contains("SSIM") && metrics.contains("max ΔE"));
+
+    assert!(images_match(fixture, output, 0.0), "compare split diagonal output differs from fixture");
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn compare_side_by_side() {
+    let fixture = "tests/fixtures/compare/side_by_side.png";
+    let output = "tests/fixtures/compare/side_by_side_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let (_, elapsed) = run_compare("lena.png", output, &[AFTER, "-m", "side-by-side", "-l", "Original,Velvia 50"]);
+    println!("compare side-by-side latency: {elapsed:?}");
+
+    assert!(images_match(fixture, output, 0.0), "compare side-by-side output differs from fixture");
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn compare_difference() {
+    let fixture = "tests/fixtures/compare/difference.png";
+    let output = "tests/fixtures/compare/difference_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let (_, elapsed) = run_compare("lena.png", output, &[AFTER, "-m", "difference"]);
+    println!("compare difference latency: {elapsed:?}");
+
+    assert!(images_match(fixture, output, 0.0), "compare difference output differs from fixture");
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn compare_tolerance() {
+    let output = "tests/fixtures/compare/tolerance_actual.png";
+    let (metrics, _) = run_compare("lena.png", output, &["lena.png"]);
+    assert!(metrics.contains("PSNR inf"), "identical images reported: {metrics}");
+    std::fs::remove_file(output).ok();
+
+    // A JPEG round trip is close but not identical
+    let jpeg = "tests/fixtures/compare/tolerance_actual.jpg";
+    image::open("lena.png").unwrap().save(jpeg).unwrap();
+    assert!(!images_match("lena.png", jpeg, 0.0));
+    assert!(images_match("lena.png", jpeg, 25.0));
+    assert!(!images_match("lena.png", AFTER, 1.0));
+    // Different sizes never match, whatever the tolerance
+    assert!(!images_match("lena.png", "tests/fixtures/composite/logo.png", 1000.0));
+    std::fs::remove_file(jpeg).ok();
+}
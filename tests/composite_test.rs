@@ -0,0 +1,81 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_composite(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "composite"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli composite {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn composite_screen_cover_texture() {
+    let fixture = "tests/fixtures/composite/screen_cover_texture.png";
+    let output = "tests/fixtures/composite/screen_cover_texture_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_composite(
+        "lena.png",
+        output,
+        &["tests/fixtures/composite/texture.png", "-m", "screen", "--cover", "--opacity", "70"],
+    );
+    println!("composite screen cover texture latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "composite screen cover texture output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn composite_logo_south_east() {
+    let fixture = "tests/fixtures/composite/logo_south_east.png";
+    let output = "tests/fixtures/composite/logo_south_east_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_composite(
+        "lena.png",
+        output,
+        &["tests/fixtures/composite/logo.png", "-w", "25%", "-g", "south-east", "-x", "-10", "-y", "-10"],
+    );
+    println!("composite logo south-east latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "composite logo south-east output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn composite_stdin_layer_soft_light() {
+    let fixture = "tests/fixtures/composite/stdin_soft_light.png";
+    let output = "tests/fixtures/composite/stdin_soft_light_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let layer = std::fs::File::open("tests/fixtures/composite/flipped.png").expect("failed to open layer");
+    let status = Command::new(imagecli_bin())
+        .args(["-i", "lena.png", "-o", output, "composite", "-", "-m", "soft-light"])
+        .stdin(layer)
+        .status()
+        .expect("failed to execute imagecli");
+    assert!(status.success(), "imagecli composite from stdin failed");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "composite stdin soft-light output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
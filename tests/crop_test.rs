@@ -0,0 +1,98 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_crop(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "crop"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli crop {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn crop_rect_mixed_units() {
+    let fixture = "tests/fixtures/crop/rect_mixed.png";
+    let output = "tests/fixtures/crop/rect_mixed_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_crop(
+        "lena.png",
+        output,
+        &["-x", "10%", "-y", "100", "--width", "200", "--height", "50%"],
+    );
+    println!("crop rect (px and %) latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "crop rect (px and %) output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn crop_aspect_16_9() {
+    let fixture = "tests/fixtures/crop/aspect_16_9.png";
+    let output = "tests/fixtures/crop/aspect_16_9_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_crop("lena.png", output, &["--aspect", "16:9"]);
+    println!("crop 16:9 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "crop 16:9 output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn crop_aspect_gravity() {
+    let fixture = "tests/fixtures/crop/aspect_4_5_west.png";
+    let output = "tests/fixtures/crop/aspect_4_5_west_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_crop("lena.png", output, &["--aspect", "4:5", "--gravity", "west"]);
+    println!("crop 4:5 west latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "crop 4:5 west output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn crop_smart_with_debug_overlay() {
+    let fixture = "tests/fixtures/crop/smart_1_2.png";
+    let output = "tests/fixtures/crop/smart_1_2_actual.png";
+    let debug = "tests/fixtures/crop/smart_1_2_debug_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_crop("lena.png", output, &["--aspect", "1:2", "--smart", "--debug", debug]);
+    println!("crop smart 1:2 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "crop smart 1:2 output differs from fixture"
+    );
+
+    let img = image::open(output).unwrap();
+    assert_eq!((img.width(), img.height()), (256, 512));
+
+    // The overlay keeps the full frame so the chosen window can be judged in context
+    let overlay = image::open(debug).unwrap();
+    assert_eq!((overlay.width(), overlay.height()), (512, 512));
+
+    std::fs::remove_file(output).ok();
+    std::fs::remove_file(debug).ok();
+}
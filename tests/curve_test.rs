@@ -2,13 +2,8 @@ use std::path::Path;
 use std::process::Command;
 use std::time::Instant;
 
-fn imagecli_bin() -> std::path::PathBuf {
-    let mut path = std::env::current_exe().unwrap();
-    path.pop();
-    path.pop();
-    path.push("imagecli");
-    path
-}
+mod common;
+use common::{imagecli_bin, images_match};
 
 fn run_curve(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
     let mut cmd_args = vec!["-i", input, "-o", output, "curve"];
@@ -24,17 +19,6 @@ fn run_curve(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
     elapsed
 }
 
-fn images_are_identical(path_a: &str, path_b: &str) -> bool {
-    let a = image::open(path_a).expect("failed to open image A").to_rgb8();
-    let b = image::open(path_b).expect("failed to open image B").to_rgb8();
-
-    if a.dimensions() != b.dimensions() {
-        return false;
-    }
-
-    a.pixels().zip(b.pixels()).all(|(pa, pb)| pa == pb)
-}
-
 #[test]
 fn curve_s_contrast() {
     let fixture = "tests/fixtures/curve/s_curve.png";
@@ -45,7 +29,7 @@ fn curve_s_contrast() {
     println!("curve S-curve latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "curve S-curve output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -61,7 +45,7 @@ fn curve_faded() {
     println!("curve faded/matte latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "curve faded output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -81,7 +65,7 @@ fn curve_full_5pt() {
     println!("curve full 5-point latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "curve full 5-point output differs from fixture"
     );
     std::fs::remove_file(output).ok();
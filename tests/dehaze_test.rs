@@ -0,0 +1,68 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_dehaze(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "dehaze"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli dehaze {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn dehaze_default() {
+    let fixture = "tests/fixtures/dehaze/default.png";
+    let output = "tests/fixtures/dehaze/default_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_dehaze("lena.png", output, &[]);
+    println!("dehaze default latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "dehaze default output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn dehaze_strong() {
+    let fixture = "tests/fixtures/dehaze/strong.png";
+    let output = "tests/fixtures/dehaze/strong_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_dehaze("lena.png", output, &["--amount", "80"]);
+    println!("dehaze strong latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "dehaze strong output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn dehaze_add_haze() {
+    let fixture = "tests/fixtures/dehaze/add_haze.png";
+    let output = "tests/fixtures/dehaze/add_haze_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_dehaze("lena.png", output, &["--amount=-60"]);
+    println!("dehaze add haze latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "dehaze add haze output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
@@ -0,0 +1,88 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_film(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "film"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli film {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn film_portra_400() {
+    let fixture = "tests/fixtures/film/portra_400.png";
+    let output = "tests/fixtures/film/portra_400_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_film("lena.png", output, &["--stock", "portra-400"]);
+    println!("film portra-400 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "film portra-400 output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn film_velvia_no_grain() {
+    let fixture = "tests/fixtures/film/velvia_50_no_grain.png";
+    let output = "tests/fixtures/film/velvia_50_no_grain_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_film("lena.png", output, &["--stock", "velvia-50", "--no-grain"]);
+    println!("film velvia-50 without grain latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "film velvia-50 without grain output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn film_custom_stock_file() {
+    let fixture = "tests/fixtures/film/custom_stock.png";
+    let output = "tests/fixtures/film/custom_stock_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_film(
+        "lena.png",
+        output,
+        &["--stock", "tests/fixtures/film/custom_stock.json"],
+    );
+    println!("film custom stock file latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "film custom stock file output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn film_tri_x_frame_3() {
+    let fixture = "tests/fixtures/film/tri_x_400_frame_3.png";
+    let output = "tests/fixtures/film/tri_x_400_frame_3_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_film("lena.png", output, &["--stock", "tri-x-400", "--frame", "3"]);
+    println!("film tri-x-400 frame 3 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "film tri-x-400 frame 3 output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
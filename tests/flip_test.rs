@@ -0,0 +1,52 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_flip(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "flip"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli flip {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn flip_horizontal() {
+    let fixture = "tests/fixtures/flip/horizontal.png";
+    let output = "tests/fixtures/flip/horizontal_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_flip("lena.png", output, &["horizontal"]);
+    println!("flip horizontal latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "flip horizontal output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn flip_vertical() {
+    let fixture = "tests/fixtures/flip/vertical.png";
+    let output = "tests/fixtures/flip/vertical_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_flip("lena.png", output, &["vertical"]);
+    println!("flip vertical latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "flip vertical output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
@@ -0,0 +1,68 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_frame(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "frame"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli frame {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn frame_asymmetric() {
+    let fixture = "tests/fixtures/frame/asymmetric.png";
+    let output = "tests/fixtures/frame/asymmetric_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_frame("lena.png", output, &["-b", "20", "--bottom", "80", "-c", "#202020"]);
+    println!("frame asymmetric latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "frame asymmetric output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn frame_pad_to_4_5() {
+    let fixture = "tests/fixtures/frame/pad_to_4_5.png";
+    let output = "tests/fixtures/frame/pad_to_4_5_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_frame("lena.png", output, &["-b", "3%", "-a", "4:5"]);
+    println!("frame pad to 4:5 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "frame pad to 4:5 output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn frame_template_35mm() {
+    let fixture = "tests/fixtures/frame/template_35mm.png";
+    let output = "tests/fixtures/frame/template_35mm_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_frame("lena.png", output, &["-t", "35mm"]);
+    println!("frame 35mm template latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "frame 35mm template output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
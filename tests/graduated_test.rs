@@ -0,0 +1,72 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_graduated(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "graduated"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli graduated {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn graduated_default_darken() {
+    let fixture = "tests/fixtures/graduated/darken_end_40.png";
+    let output = "tests/fixtures/graduated/darken_end_40_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_graduated("lena.png", output, &["--end", "40", "-e", "-1.5", "-t", "-30"]);
+    println!("graduated darken sky end 40 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "graduated darken sky end 40 output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn graduated_angled_clarity() {
+    let fixture = "tests/fixtures/graduated/angle_30_clarity.png";
+    let output = "tests/fixtures/graduated/angle_30_clarity_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_graduated(
+        "lena.png",
+        output,
+        &["--angle", "30", "--start", "20", "--end", "70", "-c", "50", "-s", "30"],
+    );
+    println!("graduated angled clarity latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "graduated angled clarity output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn graduated_bottom_up() {
+    let fixture = "tests/fixtures/graduated/bottom_up.png";
+    let output = "tests/fixtures/graduated/bottom_up_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_graduated("lena.png", output, &["--angle", "-90", "--end", "30", "-t", "40"]);
+    println!("graduated bottom up warm latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "graduated bottom up warm output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
@@ -2,13 +2,8 @@ use std::path::Path;
 use std::process::Command;
 use std::time::Instant;
 
-fn imagecli_bin() -> std::path::PathBuf {
-    let mut path = std::env::current_exe().unwrap();
-    path.pop();
-    path.pop();
-    path.push("imagecli");
-    path
-}
+mod common;
+use common::{imagecli_bin, images_match};
 
 fn run_grain(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
     let mut cmd_args = vec!["-i", input, "-o", output, "grain"];
@@ -24,17 +19,6 @@ fn run_grain(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
     elapsed
 }
 
-fn images_are_identical(path_a: &str, path_b: &str) -> bool {
-    let a = image::open(path_a).expect("failed to open image A").to_rgb8();
-    let b = image::open(path_b).expect("failed to open image B").to_rgb8();
-
-    if a.dimensions() != b.dimensions() {
-        return false;
-    }
-
-    a.pixels().zip(b.pixels()).all(|(pa, pb)| pa == pb)
-}
-
 #[test]
 fn grain_default() {
     let fixture = "tests/fixtures/grain/default.png";
@@ -45,7 +29,7 @@ fn grain_default() {
     println!("grain default latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "grain default output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -65,7 +49,7 @@ fn grain_fine_sharp() {
     println!("grain fine sharp latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "grain fine sharp output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -85,8 +69,104 @@ fn grain_coarse_smooth() {
     println!("grain coarse smooth latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "grain coarse smooth output differs from fixture"
     );
     std::fs::remove_file(output).ok();
 }
+
+#[test]
+fn grain_seeded_frame() {
+    let fixture = "tests/fixtures/grain/seed_7_frame_3.png";
+    let output = "tests/fixtures/grain/seed_7_frame_3_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_grain("lena.png", output, &["--seed", "7", "--frame", "3"]);
+    println!("grain seed 7 frame 3 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "grain seed 7 frame 3 output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn grain_content_seed_monochrome() {
+    let fixture = "tests/fixtures/grain/content_seed_mono.png";
+    let output = "tests/fixtures/grain/content_seed_mono_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_grain("lena.png", output, &["--seed-from-content", "--monochrome"]);
+    println!("grain content seed monochrome latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "grain content seed monochrome output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn grain_frames_differ() {
+    let frame_a = "tests/fixtures/grain/frame_a_actual.png";
+    let frame_b = "tests/fixtures/grain/frame_b_actual.png";
+
+    run_grain("lena.png", frame_a, &["--seed", "7", "--frame", "4"]);
+    run_grain("lena.png", frame_b, &["--seed", "7", "--frame", "5"]);
+
+    assert!(
+        !images_match(frame_a, frame_b, 0.0),
+        "consecutive frames should get different grain"
+    );
+    std::fs::remove_file(frame_a).ok();
+    std::fs::remove_file(frame_b).ok();
+}
+
+#[test]
+fn grain_profile_portra_400() {
+    let fixture = "tests/fixtures/grain/profile_portra_400.png";
+    let output = "tests/fixtures/grain/profile_portra_400_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_grain("lena.png", output, &["--profile", "portra-400"]);
+    println!("grain profile portra-400 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "grain profile portra-400 output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn grain_profile_tri_x_400() {
+    let fixture = "tests/fixtures/grain/profile_tri_x_400.png";
+    let output = "tests/fixtures/grain/profile_tri_x_400_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_grain("lena.png", output, &["--profile", "tri-x-400"]);
+    println!("grain profile tri-x-400 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "grain profile tri-x-400 output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn grain_poisson_coarse() {
+    let fixture = "tests/fixtures/grain/poisson_coarse.png";
+    let output = "tests/fixtures/grain/poisson_coarse_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_grain("lena.png", output, &["--model", "poisson", "-a", "40", "-s", "80", "-r", "30"]);
+    println!("grain poisson coarse latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "grain poisson coarse output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
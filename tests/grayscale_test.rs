@@ -2,23 +2,8 @@ use std::path::Path;
 use std::process::Command;
 use std::time::Instant;
 
-fn imagecli_bin() -> std::path::PathBuf {
-    let mut path = std::env::current_exe().unwrap();
-    path.pop();
-    path.pop();
-    path.push("imagecli");
-    path
-}
-
-fn images_are_identical(path_a: &str, path_b: &str) -> bool {
-    let a = image::open(path_a).expect("failed to open image A");
-    let b = image::open(path_b).expect("failed to open image B");
-
-    let a = a.as_bytes();
-    let b = b.as_bytes();
-
-    a == b
-}
+mod common;
+use common::{imagecli_bin, images_match};
 
 #[test]
 fn grayscale_default() {
@@ -36,7 +21,7 @@ fn grayscale_default() {
     println!("grayscale latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "grayscale output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -80,9 +65,81 @@ fn grayscale_idempotent() {
     println!("grayscale idempotent (2 passes) latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(pass1, pass2),
+        images_match(pass1, pass2, 0.0),
         "grayscale is not idempotent: second pass differs from first"
     );
     std::fs::remove_file(pass1).ok();
     std::fs::remove_file(pass2).ok();
 }
+
+#[test]
+fn grayscale_red_filter() {
+    let fixture = "tests/fixtures/grayscale/red_filter.png";
+    let output = "tests/fixtures/grayscale/red_filter_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(["-i", "lena.png", "-o", output, "grayscale", "--filter", "red"])
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli grayscale --filter red failed");
+    println!("grayscale red filter latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "grayscale red filter output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn grayscale_custom_weights() {
+    let fixture = "tests/fixtures/grayscale/weights_blue_heavy.png";
+    let output = "tests/fixtures/grayscale/weights_blue_heavy_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(["-i", "lena.png", "-o", output, "grayscale", "--weights", "10,30,60"])
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli grayscale --weights 10,30,60 failed");
+    println!("grayscale custom weights latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "grayscale custom weights output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn grayscale_red_filter_keeps_alpha() {
+    let input = "tests/fixtures/composite/logo.png";
+    let fixture = "tests/fixtures/grayscale/red_filter_alpha.png";
+    let output = "tests/fixtures/grayscale/red_filter_alpha_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let status = Command::new(imagecli_bin())
+        .args(["-i", input, "-o", output, "grayscale", "--filter", "red"])
+        .status()
+        .expect("failed to execute imagecli");
+    assert!(status.success());
+
+    let original = image::open(input).unwrap().to_rgba8();
+    let result = image::open(output).unwrap();
+    assert!(result.color().has_alpha(), "grayscale dropped the alpha channel");
+    let result = result.to_rgba8();
+    assert!(
+        original.pixels().zip(result.pixels()).all(|(a, b)| a[3] == b[3]),
+        "grayscale changed the alpha channel"
+    );
+    assert!(
+        images_match(fixture, output, 0.0),
+        "grayscale red filter with alpha output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
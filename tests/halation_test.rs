@@ -0,0 +1,68 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_halation(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "halation"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli halation {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn halation_default() {
+    let fixture = "tests/fixtures/halation/default.png";
+    let output = "tests/fixtures/halation/default_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_halation("lena.png", output, &[]);
+    println!("halation default latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "halation default output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn halation_bloom() {
+    let fixture = "tests/fixtures/halation/bloom_a60_t60.png";
+    let output = "tests/fixtures/halation/bloom_a60_t60_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_halation("lena.png", output, &["--mode", "bloom", "-a", "60", "-t", "60"]);
+    println!("halation bloom amount 60 threshold 60 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "halation bloom amount 60 threshold 60 output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn halation_cyan_wide() {
+    let fixture = "tests/fixtures/halation/hue_190_r60.png";
+    let output = "tests/fixtures/halation/hue_190_r60_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_halation("lena.png", output, &["--hue", "190", "-r", "60", "-a", "80"]);
+    println!("halation cyan hue radius 60 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "halation cyan hue radius 60 output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
@@ -0,0 +1,80 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_hsl(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "hsl"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli hsl {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn hsl_shift_greens() {
+    let fixture = "tests/fixtures/hsl/greens_to_yellow.png";
+    let output = "tests/fixtures/hsl/greens_to_yellow_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_hsl(
+        "lena.png",
+        output,
+        &["--green-hue=-50", "--yellow-sat", "30", "--aqua-hue=-20"],
+    );
+    println!("hsl greens to yellow latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "hsl greens to yellow output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn hsl_desaturate_purples() {
+    let fixture = "tests/fixtures/hsl/desat_purples.png";
+    let output = "tests/fixtures/hsl/desat_purples_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_hsl(
+        "lena.png",
+        output,
+        &["--purple-sat=-100", "--magenta-sat=-80", "--blue-lum=-40"],
+    );
+    println!("hsl desaturate purples latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "hsl desaturate purples output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn hsl_warm_skin() {
+    let fixture = "tests/fixtures/hsl/warm_skin.png";
+    let output = "tests/fixtures/hsl/warm_skin_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_hsl(
+        "lena.png",
+        output,
+        &["--red-hue", "40", "--orange-sat", "-25", "--orange-lum", "30"],
+    );
+    println!("hsl warm skin latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "hsl warm skin output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
@@ -0,0 +1,68 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_lens_fx(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "lens-fx"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli lens-fx {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn lens_fx_default() {
+    let fixture = "tests/fixtures/lens-fx/default.png";
+    let output = "tests/fixtures/lens-fx/default_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_lens_fx("lena.png", output, &[]);
+    println!("lens-fx default latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "lens-fx default output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn lens_fx_barrel_glow() {
+    let fixture = "tests/fixtures/lens-fx/barrel_60_glow_40.png";
+    let output = "tests/fixtures/lens-fx/barrel_60_glow_40_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_lens_fx("lena.png", output, &["-a", "100", "-d", "60", "-g", "40"]);
+    println!("lens-fx barrel 60 glow 40 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "lens-fx barrel 60 glow 40 output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn lens_fx_pincushion_rect() {
+    let fixture = "tests/fixtures/lens-fx/pincushion_rect.png";
+    let output = "tests/fixtures/lens-fx/pincushion_rect_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_lens_fx("lena.png", output, &["-a", "-50", "-d", "-40", "-r", "-100"]);
+    println!("lens-fx pincushion rectangular latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "lens-fx pincushion rectangular output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
@@ -0,0 +1,98 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_masked(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn mask_hue_splash() {
+    let fixture = "tests/fixtures/mask/hue_splash.png";
+    let output = "tests/fixtures/mask/hue_splash_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_masked("lena.png", output, &["grayscale", "--mask", "hue:hue=270,width=80", "--mask-invert"]);
+    println!("mask grayscale outside a purple hue mask latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "mask grayscale outside a purple hue mask output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn mask_linear_minus_radial() {
+    let fixture = "tests/fixtures/mask/linear_minus_radial.png";
+    let output = "tests/fixtures/mask/linear_minus_radial_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_masked("lena.png", output, &["basic", "--exposure=-2", "--mask", "linear:angle=90,start=0,end=50", "--mask=-radial:x=50,y=50,radius=20,feather=20", "--mask-feather", "10"]);
+    println!("mask linear gradient minus radial, feathered latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "mask linear gradient minus radial, feathered output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn mask_image_times_luma() {
+    let fixture = "tests/fixtures/mask/image_times_luma.png";
+    let output = "tests/fixtures/mask/image_times_luma_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_masked("lena.png", output, &["color", "--saturation=80", "--mask", "image:tests/fixtures/mask/source_mask.png", "--mask=*luma:min=40,max=80"]);
+    println!("mask image mask intersected with luma range latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "mask image mask intersected with luma range output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn mask_strength_with_inverted_hue() {
+    // --strength scales the mask: 70% desaturation everywhere but the reds
+    let fixture = "tests/fixtures/mask/strength_with_mask.png";
+    let output = "tests/fixtures/mask/strength_with_mask_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_masked("lena.png", output, &["color", "--saturation=-100", "--mask", "hue:hue=0,width=60", "--mask-invert", "--strength", "70"]);
+    println!("mask strength 70 with inverted hue mask latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "mask strength 70 with inverted hue mask output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn mask_rejects_size_changes() {
+    let output = "tests/fixtures/mask/resize_actual.png";
+    let result = Command::new(imagecli_bin())
+        .args(["-i", "lena.png", "-o", output, "resize", "-w", "100", "--mask", "luma:min=0,max=50"])
+        .output()
+        .expect("failed to execute imagecli");
+    assert!(!result.status.success(), "--mask with resize should fail");
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(stderr.contains("it can change the image size"), "unexpected error: {stderr}");
+    assert!(!Path::new(output).exists(), "nothing should be written when --mask is rejected");
+}
@@ -0,0 +1,56 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_pad(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "pad"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli pad {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn pad_square_center() {
+    let fixture = "tests/fixtures/pad/square_center.png";
+    let output = "tests/fixtures/pad/square_center_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_pad("lena.png", output, &["--width", "640", "--height", "640"]);
+    println!("pad centered latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "pad centered output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn pad_gravity_color() {
+    let fixture = "tests/fixtures/pad/south_east_color.png";
+    let output = "tests/fixtures/pad/south_east_color_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_pad(
+        "lena.png",
+        output,
+        &["-w", "700", "-H", "600", "--color", "#336699", "--gravity", "south-east"],
+    );
+    println!("pad south-east color latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "pad south-east color output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
@@ -0,0 +1,248 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_preset(input: &str, output: &str, args: &[&str]) -> (std::process::Output, std::time::Duration) {
+    let mut cmd_args = vec!["-i", input, "-o", output, "preset"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let result = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .output()
+        .expect("failed to execute imagecli");
+    (result, start.elapsed())
+}
+
+#[test]
+fn preset_contact_sheet_selected() {
+    let fixture = "tests/fixtures/preset/contact_sheet.png";
+    let output = "tests/fixtures/preset/contact_sheet_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let (result, elapsed) = run_preset(
+        "lena.png",
+        output,
+        &["contact-sheet", "kodak-portra-400", "ilford-hp5", "--original", "-s", "160"],
+    );
+    println!("preset contact-sheet latency: {elapsed:?}");
+    assert!(result.status.success(), "imagecli preset contact-sheet failed");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "preset contact sheet output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn preset_contact_sheet_custom_file() {
+    // Exercises a positional argument, an array, and a global --mask inside a step
+    let fixture = "tests/fixtures/preset/mirror_mono.png";
+    let output = "tests/fixtures/preset/mirror_mono_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let (result, elapsed) = run_preset(
+        "lena.png",
+        output,
+        &["contact-sheet", "tests/fixtures/preset/mirror_mono.json", "-s", "200"],
+    );
+    println!("preset contact-sheet custom file latency: {elapsed:?}");
+    assert!(result.status.success(), "imagecli preset contact-sheet failed");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "preset contact sheet custom file output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn preset_contact_sheet_bad_step() {
+    let dir = "tests/fixtures/preset/bad_step_actual";
+    std::fs::create_dir_all(dir).unwrap();
+    let preset = format!("{dir}/bad.json");
+    std::fs::write(
+        &preset,
+        r#"{ "version": 1, "name": "Bad", "pipeline": [{ "command": "vignette", "args": { "amout": -20 } }] }"#,
+    )
+    .unwrap();
+
+    let (result, _) = run_preset("lena.png", &format!("{dir}/sheet.png"), &["contact-sheet", &preset]);
+    assert!(!result.status.success(), "a preset with an unknown argument should fail");
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(stderr.contains("preset 'Bad', step 'vignette'"), "unexpected error: {stderr}");
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn preset_contact_sheet_extends() {
+    // kodak-portra-160 overrides three of kodak-portra-400's steps and appends a grain step
+    let fixture = "tests/fixtures/preset/portra_160.png";
+    let output = "tests/fixtures/preset/portra_160_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let (result, elapsed) = run_preset("lena.png", output, &["contact-sheet", "kodak-portra-160", "-s", "200"]);
+    println!("preset contact-sheet extends latency: {elapsed:?}");
+    assert!(result.status.success(), "imagecli preset contact-sheet failed");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "preset contact sheet extends output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn preset_validate_bundled() {
+    let result = Command::new(imagecli_bin())
+        .args(["preset", "validate"])
+        .output()
+        .expect("failed to execute imagecli");
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(result.status.success(), "bundled presets failed validation: {}", String::from_utf8_lossy(&result.stderr));
+    assert!(stdout.contains("kodak-portra-160.json: ok"), "unexpected output: {stdout}");
+}
+
+#[test]
+fn preset_validate_problems() {
+    let dir = "tests/fixtures/preset/validate_actual";
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(
+        format!("{dir}/typos.json"),
+        r#"{ "version": 1, "name": "Typos", "pipeline": [
+            { "command": "color-grade", "args": { "shadow-hue": 30 } },
+            { "command": "vignette", "args": { "amount": -150 } },
+            { "command": "analyze" },
+            { "command": "channel-mixer", "args": { "red": [100, 0] } },
+            { "command": "resize", "args": { "output-size": 100, "mask": "luma:min=50" } }
+        ] }"#,
+    )
+    .unwrap();
+    std::fs::write(format!("{dir}/future.json"), r#"{ "version": 99, "name": "Future", "pipeline": [] }"#).unwrap();
+    std::fs::write(format!("{dir}/loop.json"), r#"{ "version": 1, "name": "Loop", "extends": "loop" }"#).unwrap();
+    std::fs::write(format!("{dir}/unversioned.json"), r#"{ "name": "Unversioned", "pipeline": [] }"#).unwrap();
+
+    let result = Command::new(imagecli_bin())
+        .args(["preset", "validate", "--dir", dir])
+        .output()
+        .expect("failed to execute imagecli");
+    assert!(!result.status.success(), "invalid presets should fail validation");
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    for expected in [
+        "step 1 (color-grade): unexpected argument '--shadow-hue' found; tip: a similar argument exists: '--shadows-hue'",
+        "step 2 (vignette): invalid value '-150' for '--amount <AMOUNT>': -150 is not in -100..=100",
+        "step 3 (analyze): this command does not transform an image",
+        "step 4 (channel-mixer): invalid value '100,0' for '--red <RED>': expected 3 comma-separated values, got 2",
+        "step 5 (resize): --mask can't be used with this command: it can change the image size",
+        "has version 99",
+        "extends itself through loop",
+    ] {
+        assert!(stderr.contains(expected), "missing {expected:?} in: {stderr}");
+    }
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(stdout.contains("unversioned.json: ok"), "a preset without a version should read as version 1: {stdout}");
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn preset_apply_strength() {
+    let fixture = "tests/fixtures/preset/portra_400_strength_60.png";
+    let output = "tests/fixtures/preset/portra_400_strength_60_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let (result, elapsed) = run_preset("lena.png", output, &["apply", "kodak-portra-400", "--strength", "60"]);
+    println!("preset apply strength 60 latency: {elapsed:?}");
+    assert!(result.status.success(), "imagecli preset apply failed");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "preset apply strength 60 output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn preset_apply_strength_zero() {
+    let output = "tests/fixtures/preset/strength_zero_actual.png";
+    let (result, _) = run_preset("lena.png", output, &["apply", "fuji-velvia-50", "--strength", "0"]);
+    assert!(result.status.success(), "imagecli preset apply failed");
+    assert!(images_match("lena.png", output, 0.0), "--strength 0 should return the input unchanged");
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn preset_contact_sheet_strength() {
+    let fixture = "tests/fixtures/preset/contact_sheet_strength_50.png";
+    let output = "tests/fixtures/preset/contact_sheet_strength_50_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let (result, elapsed) = run_preset(
+        "lena.png",
+        output,
+        &["contact-sheet", "kodak-portra-400", "ilford-hp5", "--original", "-s", "160", "--strength", "50"],
+    );
+    println!("preset contact-sheet strength 50 latency: {elapsed:?}");
+    assert!(result.status.success(), "imagecli preset contact-sheet failed");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "preset contact sheet strength 50 output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn preset_strength_rejects_analyze() {
+    let result = Command::new(imagecli_bin())
+        .args(["-i", "lena.png", "analyze", "--strength", "60"])
+        .output()
+        .expect("failed to execute imagecli");
+    assert!(!result.status.success(), "--strength with analyze should fail");
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(stderr.contains("it doesn't transform an image"), "unexpected error: {stderr}");
+}
+
+#[test]
+fn preset_apply_masked_resize_step() {
+    let dir = "tests/fixtures/preset/masked_resize_actual";
+    std::fs::create_dir_all(dir).unwrap();
+    let preset = format!("{dir}/masked.json");
+    std::fs::write(
+        &preset,
+        r#"{ "name": "Masked", "pipeline": [{ "command": "resize", "args": { "output-size": 100, "strength": 50 } }] }"#,
+    )
+    .unwrap();
+
+    let (result, _) = run_preset("lena.png", &format!("{dir}/out.png"), &["apply", &preset]);
+    assert!(!result.status.success(), "a step that can't blend should fail");
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(
+        stderr.contains("preset 'Masked', step 'resize': --strength can't be used with this command"),
+        "unexpected error: {stderr}"
+    );
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn preset_contact_sheet_strength_resizing_preset() {
+    let dir = "tests/fixtures/preset/strength_resize_actual";
+    std::fs::create_dir_all(dir).unwrap();
+    let preset = format!("{dir}/small.json");
+    std::fs::write(&preset, r#"{ "name": "Small", "pipeline": [{ "command": "resize", "args": { "output-size": 20 } }] }"#)
+        .unwrap();
+
+    let output = format!("{dir}/sheet.png");
+    let (result, _) = run_preset("lena.png", &output, &["contact-sheet", &preset, "--size", "64", "--strength", "50"]);
+    assert!(!result.status.success(), "--strength with a resizing preset should fail");
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(
+        stderr.contains("--strength can't be used with preset 'Small': it can change the image size"),
+        "unexpected error: {stderr}"
+    );
+    assert!(!Path::new(&output).exists(), "nothing should be written when --strength is rejected");
+    std::fs::remove_dir_all(dir).ok();
+}
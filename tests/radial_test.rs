@@ -0,0 +1,92 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_radial(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "radial"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli radial {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn radial_face_brighten() {
+    let fixture = "tests/fixtures/radial/face_brighten.png";
+    let output = "tests/fixtures/radial/face_brighten_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_radial(
+        "lena.png",
+        output,
+        &["-x", "55", "-y", "55", "--width", "18", "--height", "25", "-e", "0.7", "-c", "20"],
+    );
+    println!("radial face brighten latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "radial face brighten output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn radial_inverted() {
+    let fixture = "tests/fixtures/radial/inverted_darken.png";
+    let output = "tests/fixtures/radial/inverted_darken_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_radial("lena.png", output, &["--invert", "-e", "-1", "-s", "-50"]);
+    println!("radial inverted darken latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "radial inverted darken output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn radial_rect_hard() {
+    let fixture = "tests/fixtures/radial/rect_hard.png";
+    let output = "tests/fixtures/radial/rect_hard_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_radial("lena.png", output, &["-r", "-100", "-f", "0", "-t", "50"]);
+    println!("radial rectangular hard edge latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "radial rectangular hard edge output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn radial_matches_mask() {
+    let output = "tests/fixtures/radial/matches_mask_actual.png";
+    let masked = "tests/fixtures/radial/matches_mask_masked_actual.png";
+    run_radial("lena.png", output, &["--width", "20", "--height", "30", "--feather", "50", "-e", "1"]);
+    let status = Command::new(imagecli_bin())
+        .args(["-i", "lena.png", "-o", masked, "basic", "--exposure", "1"])
+        .args(["--mask", "radial:width=20,height=30,feather=50"])
+        .status()
+        .expect("failed to execute imagecli");
+    assert!(status.success(), "imagecli basic --mask radial failed");
+
+    assert!(
+        images_match(output, masked, 0.0),
+        "radial filter differs from the same --mask radial shape"
+    );
+    std::fs::remove_file(output).ok();
+    std::fs::remove_file(masked).ok();
+}
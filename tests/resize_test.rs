@@ -2,13 +2,8 @@ use std::path::Path;
 use std::process::Command;
 use std::time::Instant;
 
-fn imagecli_bin() -> std::path::PathBuf {
-    let mut path = std::env::current_exe().unwrap();
-    path.pop();
-    path.pop();
-    path.push("imagecli");
-    path
-}
+mod common;
+use common::{imagecli_bin, images_match};
 
 fn run_resize(input: &str, output: &str, size: u32) -> std::time::Duration {
     let start = Instant::now();
@@ -21,15 +16,18 @@ fn run_resize(input: &str, output: &str, size: u32) -> std::time::Duration {
     elapsed
 }
 
-fn images_are_identical(path_a: &str, path_b: &str) -> bool {
-    let a = image::open(path_a).expect("failed to open image A").to_rgb8();
-    let b = image::open(path_b).expect("failed to open image B").to_rgb8();
-
-    if a.dimensions() != b.dimensions() {
-        return false;
-    }
+fn run_resize_args(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "resize"];
+    cmd_args.extend_from_slice(args);
 
-    a.pixels().zip(b.pixels()).all(|(pa, pb)| pa == pb)
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli resize {args:?} failed");
+    elapsed
 }
 
 #[test]
@@ -42,7 +40,7 @@ fn resize_128() {
     println!("resize 128 latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "resize 128 output differs from fixture"
     );
 
@@ -63,7 +61,7 @@ fn resize_256() {
     println!("resize 256 latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "resize 256 output differs from fixture"
     );
 
@@ -84,7 +82,7 @@ fn resize_1024_noop() {
     println!("resize 1024 (no-op) latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "resize 1024 output differs from fixture"
     );
 
@@ -95,3 +93,103 @@ fn resize_1024_noop() {
 
     std::fs::remove_file(output).ok();
 }
+
+#[test]
+fn resize_cover_social_card() {
+    let fixture = "tests/fixtures/resize/cover_1200x630.png";
+    let output = "tests/fixtures/resize/cover_1200x630_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_resize_args(
+        "lena.png",
+        output,
+        &["--width", "1200", "--height", "630", "--mode", "cover"],
+    );
+    println!("resize cover 1200x630 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "resize cover 1200x630 output differs from fixture"
+    );
+
+    let img = image::open(output).unwrap();
+    assert_eq!(img.width(), 1200);
+    assert_eq!(img.height(), 630);
+
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn resize_fill_padded() {
+    let fixture = "tests/fixtures/resize/fill_300x200.png";
+    let output = "tests/fixtures/resize/fill_300x200_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_resize_args(
+        "lena.png",
+        output,
+        &["--width", "300", "--height", "200", "--mode", "fill", "--background", "black"],
+    );
+    println!("resize fill 300x200 latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "resize fill 300x200 output differs from fixture"
+    );
+
+    let img = image::open(output).unwrap();
+    assert_eq!(img.width(), 300);
+    assert_eq!(img.height(), 200);
+
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn resize_scale_percent_nearest() {
+    let fixture = "tests/fixtures/resize/scale_37_nearest.png";
+    let output = "tests/fixtures/resize/scale_37_nearest_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_resize_args("lena.png", output, &["--scale", "37%", "--filter", "nearest"]);
+    println!("resize scale 37% nearest latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "resize scale 37% nearest output differs from fixture"
+    );
+
+    let img = image::open(output).unwrap();
+    assert_eq!(img.width(), 189);
+    assert_eq!(img.height(), 189);
+
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn resize_upscale_width() {
+    let output = "tests/fixtures/resize/upscale_width_actual.png";
+
+    run_resize_args("lena.png", output, &["--width", "800"]);
+    let img = image::open(output).unwrap();
+    assert_eq!(img.width(), 512, "fit mode should not enlarge without --upscale");
+
+    run_resize_args("lena.png", output, &["--width", "800", "--upscale"]);
+    let img = image::open(output).unwrap();
+    assert_eq!(img.width(), 800);
+    assert_eq!(img.height(), 800);
+
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn resize_rejects_zero_width() {
+    let output = "tests/fixtures/resize/zero_width_actual.png";
+    let result = Command::new(imagecli_bin())
+        .args(["-i", "lena.png", "-o", output, "resize", "-w", "0", "-H", "10", "-m", "exact"])
+        .output()
+        .expect("failed to execute imagecli");
+    assert!(!result.status.success(), "a zero width should be rejected");
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(stderr.contains("0 is not in 1.."), "unexpected error: {stderr}");
+    assert!(!Path::new(output).exists(), "nothing should be written for a zero width");
+}
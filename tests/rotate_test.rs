@@ -0,0 +1,68 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_rotate(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "rotate"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli rotate {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn rotate_quarter_turn() {
+    let fixture = "tests/fixtures/rotate/rot_90.png";
+    let output = "tests/fixtures/rotate/rot_90_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_rotate("lena.png", output, &["--angle", "90"]);
+    println!("rotate 90 degrees latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "rotate 90 degrees output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn rotate_straighten() {
+    let fixture = "tests/fixtures/rotate/straighten_crop.png";
+    let output = "tests/fixtures/rotate/straighten_crop_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_rotate("lena.png", output, &["--angle=-4.5", "--crop"]);
+    println!("rotate straighten crop latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "rotate straighten crop output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn rotate_expand_fill() {
+    let fixture = "tests/fixtures/rotate/expand_fill.png";
+    let output = "tests/fixtures/rotate/expand_fill_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_rotate("lena.png", output, &["--angle", "30", "--fill", "#203040"]);
+    println!("rotate expand with fill latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "rotate expand with fill output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
@@ -0,0 +1,76 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_shadows_highlights(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "shadows-highlights"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli shadows-highlights {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn shadows_highlights_default() {
+    let fixture = "tests/fixtures/shadows-highlights/default.png";
+    let output = "tests/fixtures/shadows-highlights/default_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_shadows_highlights("lena.png", output, &[]);
+    println!("shadows-highlights default latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "shadows-highlights default output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn shadows_highlights_open_shadows() {
+    let fixture = "tests/fixtures/shadows-highlights/open_shadows.png";
+    let output = "tests/fixtures/shadows-highlights/open_shadows_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_shadows_highlights(
+        "lena.png",
+        output,
+        &["--shadows", "80", "--highlights", "0"],
+    );
+    println!("shadows-highlights open shadows latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "shadows-highlights open shadows output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn shadows_highlights_recover_highlights() {
+    let fixture = "tests/fixtures/shadows-highlights/recover_highlights.png";
+    let output = "tests/fixtures/shadows-highlights/recover_highlights_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_shadows_highlights(
+        "lena.png",
+        output,
+        &["--shadows", "0", "--highlights=-80"],
+    );
+    println!("shadows-highlights recover highlights latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "shadows-highlights recover highlights output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
@@ -2,13 +2,8 @@ use std::path::Path;
 use std::process::Command;
 use std::time::Instant;
 
-fn imagecli_bin() -> std::path::PathBuf {
-    let mut path = std::env::current_exe().unwrap();
-    path.pop();
-    path.pop();
-    path.push("imagecli");
-    path
-}
+mod common;
+use common::{imagecli_bin, images_match};
 
 fn run_show_curve(output: &str, args: &[&str]) -> std::time::Duration {
     let mut cmd_args = vec!["-o", output, "show-curve"];
@@ -24,17 +19,6 @@ fn run_show_curve(output: &str, args: &[&str]) -> std::time::Duration {
     elapsed
 }
 
-fn images_are_identical(path_a: &str, path_b: &str) -> bool {
-    let a = image::open(path_a).expect("failed to open image A").to_rgb8();
-    let b = image::open(path_b).expect("failed to open image B").to_rgb8();
-
-    if a.dimensions() != b.dimensions() {
-        return false;
-    }
-
-    a.pixels().zip(b.pixels()).all(|(pa, pb)| pa == pb)
-}
-
 #[test]
 fn show_curve_s_contrast() {
     let fixture = "tests/fixtures/show-curve/s_curve.png";
@@ -45,7 +29,7 @@ fn show_curve_s_contrast() {
     println!("show-curve S-curve latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "show-curve S-curve output differs from fixture"
     );
 
@@ -66,7 +50,7 @@ fn show_curve_faded() {
     println!("show-curve faded latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "show-curve faded output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -85,7 +69,7 @@ fn show_curve_full_5pt() {
     println!("show-curve full 5-point latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "show-curve full 5-point output differs from fixture"
     );
     std::fs::remove_file(output).ok();
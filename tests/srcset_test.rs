@@ -0,0 +1,73 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_srcset(input: &str, template: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", template, "srcset"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli srcset {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn srcset_widths_and_formats() {
+    let dir = "tests/fixtures/srcset/widths_actual";
+    let template = format!("{dir}/{{stem}}-{{w}}.{{ext}}");
+
+    let elapsed = run_srcset("lena.png", &template, &["--widths", "128,256", "--format", "png,jpeg"]);
+    println!("srcset 2 widths x 2 formats latency: {elapsed:?}");
+
+    for (w, ext) in [(128, "png"), (128, "jpg"), (256, "png"), (256, "jpg")] {
+        let path = format!("{dir}/lena-{w}.{ext}");
+        assert!(Path::new(&path).exists(), "missing variant: {path}");
+        let img = image::open(&path).unwrap();
+        assert_eq!(img.width(), w);
+        assert_eq!(img.height(), w);
+    }
+
+    // The largest variant uses the same Lanczos path as `resize`
+    assert!(
+        images_match("tests/fixtures/resize/size_256.png", &format!("{dir}/lena-256.png"), 0.0),
+        "srcset 256px output differs from resize fixture"
+    );
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn srcset_manifest() {
+    let dir = "tests/fixtures/srcset/manifest_actual";
+    let template = format!("{dir}/{{stem}}-{{w}}.{{ext}}");
+    let manifest = format!("{dir}/manifest.json");
+    std::fs::create_dir_all(dir).unwrap();
+
+    run_srcset(
+        "lena.png",
+        &template,
+        &["--widths", "100,200,1000", "--format", "webp", "--manifest", &manifest],
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&manifest).unwrap()).unwrap();
+    assert_eq!(json["source"], "lena");
+    let variants = json["variants"].as_array().unwrap();
+    // 1000px is larger than the 512px source and is skipped
+    assert_eq!(variants.len(), 2);
+    assert_eq!(variants[0]["width"], 200);
+    assert_eq!(variants[1]["width"], 100);
+    for v in variants {
+        let path = v["path"].as_str().unwrap();
+        let bytes = std::fs::metadata(path).unwrap().len();
+        assert_eq!(v["bytes"], bytes);
+    }
+    std::fs::remove_dir_all(dir).ok();
+}
@@ -2,13 +2,8 @@ use std::path::Path;
 use std::process::Command;
 use std::time::Instant;
 
-fn imagecli_bin() -> std::path::PathBuf {
-    let mut path = std::env::current_exe().unwrap();
-    path.pop();
-    path.pop();
-    path.push("imagecli");
-    path
-}
+mod common;
+use common::{imagecli_bin, images_match};
 
 fn run_unsharpen(input: &str, output: &str, sigma: f32, threshold: i32) -> std::time::Duration {
     let start = Instant::now();
@@ -24,17 +19,6 @@ fn run_unsharpen(input: &str, output: &str, sigma: f32, threshold: i32) -> std::
     elapsed
 }
 
-fn images_are_identical(path_a: &str, path_b: &str) -> bool {
-    let a = image::open(path_a).expect("failed to open image A").to_rgb8();
-    let b = image::open(path_b).expect("failed to open image B").to_rgb8();
-
-    if a.dimensions() != b.dimensions() {
-        return false;
-    }
-
-    a.pixels().zip(b.pixels()).all(|(pa, pb)| pa == pb)
-}
-
 #[test]
 fn unsharpen_sigma_1_threshold_3() {
     let fixture = "tests/fixtures/unsharpen/sigma_1.0_threshold_3.png";
@@ -45,7 +29,7 @@ fn unsharpen_sigma_1_threshold_3() {
     println!("unsharpen sigma=1.0 threshold=3 latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "unsharpen sigma=1.0 threshold=3 output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -61,7 +45,7 @@ fn unsharpen_sigma_2_threshold_5() {
     println!("unsharpen sigma=2.0 threshold=5 latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "unsharpen sigma=2.0 threshold=5 output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -77,7 +61,7 @@ fn unsharpen_sigma_4_threshold_10() {
     println!("unsharpen sigma=4.0 threshold=10 latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "unsharpen sigma=4.0 threshold=10 output differs from fixture"
     );
     std::fs::remove_file(output).ok();
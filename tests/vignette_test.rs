@@ -2,13 +2,8 @@ use std::path::Path;
 use std::process::Command;
 use std::time::Instant;
 
-fn imagecli_bin() -> std::path::PathBuf {
-    let mut path = std::env::current_exe().unwrap();
-    path.pop();
-    path.pop();
-    path.push("imagecli");
-    path
-}
+mod common;
+use common::{imagecli_bin, images_match};
 
 fn run_vignette(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
     let mut cmd_args = vec!["-i", input, "-o", output, "vignette"];
@@ -24,17 +19,6 @@ fn run_vignette(input: &str, output: &str, args: &[&str]) -> std::time::Duration
     elapsed
 }
 
-fn images_are_identical(path_a: &str, path_b: &str) -> bool {
-    let a = image::open(path_a).expect("failed to open image A").to_rgb8();
-    let b = image::open(path_b).expect("failed to open image B").to_rgb8();
-
-    if a.dimensions() != b.dimensions() {
-        return false;
-    }
-
-    a.pixels().zip(b.pixels()).all(|(pa, pb)| pa == pb)
-}
-
 #[test]
 fn vignette_dark_default() {
     let fixture = "tests/fixtures/vignette/dark_default.png";
@@ -45,7 +29,7 @@ fn vignette_dark_default() {
     println!("vignette default latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "vignette default output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -65,7 +49,7 @@ fn vignette_light_rectangular() {
     println!("vignette light rectangular latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "vignette light rectangular output differs from fixture"
     );
     std::fs::remove_file(output).ok();
@@ -85,7 +69,7 @@ fn vignette_heavy_circular() {
     println!("vignette heavy circular latency: {elapsed:?}");
 
     assert!(
-        images_are_identical(fixture, output),
+        images_match(fixture, output, 0.0),
         "vignette heavy circular output differs from fixture"
     );
     std::fs::remove_file(output).ok();
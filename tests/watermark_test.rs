@@ -0,0 +1,93 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_watermark(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "watermark"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli watermark {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn watermark_default_text() {
+    let fixture = "tests/fixtures/watermark/default_text.png";
+    let output = "tests/fixtures/watermark/default_text_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_watermark("lena.png", output, &["-t", "© Jane Doe 2026"]);
+    println!("watermark default text latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "watermark default text output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn watermark_tiled_rotated() {
+    let fixture = "tests/fixtures/watermark/tiled_rotated.png";
+    let output = "tests/fixtures/watermark/tiled_rotated_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_watermark(
+        "lena.png",
+        output,
+        &["-t", "PROOF", "--tile", "-r", "-30", "--opacity", "30", "-s", "60"],
+    );
+    println!("watermark tiled rotated latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "watermark tiled rotated output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn watermark_logo_north_west() {
+    let fixture = "tests/fixtures/watermark/logo_north_west.png";
+    let output = "tests/fixtures/watermark/logo_north_west_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_watermark(
+        "lena.png",
+        output,
+        &["-l", "tests/fixtures/composite/logo.png", "-s", "150", "-g", "north-west", "--opacity", "80"],
+    );
+    println!("watermark logo north-west latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "watermark logo north-west output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn watermark_two_lines_missing_fields() {
+    let fixture = "tests/fixtures/watermark/two_lines.png";
+    let output = "tests/fixtures/watermark/two_lines_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    // lena.png has no EXIF, so {artist} and {year} expand to nothing
+    let elapsed = run_watermark("lena.png", output, &["-t", "{artist} Proof  copy\nNot for {year} sale"]);
+    println!("watermark two lines with missing fields latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "watermark two lines with missing fields output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
@@ -0,0 +1,85 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+mod common;
+use common::{imagecli_bin, images_match};
+
+fn run_white_balance(input: &str, output: &str, args: &[&str]) -> std::time::Duration {
+    let mut cmd_args = vec!["-i", input, "-o", output, "white-balance"];
+    cmd_args.extend_from_slice(args);
+
+    let start = Instant::now();
+    let status = Command::new(imagecli_bin())
+        .args(&cmd_args)
+        .status()
+        .expect("failed to execute imagecli");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "imagecli white-balance {args:?} failed");
+    elapsed
+}
+
+#[test]
+fn white_balance_tungsten() {
+    let fixture = "tests/fixtures/white-balance/kelvin_3200.png";
+    let output = "tests/fixtures/white-balance/kelvin_3200_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_white_balance("lena.png", output, &["--kelvin", "3200", "--tint", "8"]);
+    println!("white-balance 3200K latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "white-balance 3200K output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn white_balance_gray_world() {
+    let fixture = "tests/fixtures/white-balance/gray_world.png";
+    let output = "tests/fixtures/white-balance/gray_world_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_white_balance("lena.png", output, &["--auto", "gray-world"]);
+    println!("white-balance gray-world latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "white-balance gray-world output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn white_balance_sample_cat02() {
+    let fixture = "tests/fixtures/white-balance/sample_cat02.png";
+    let output = "tests/fixtures/white-balance/sample_cat02_actual.png";
+    assert!(Path::new(fixture).exists(), "fixture missing: {fixture}");
+
+    let elapsed = run_white_balance(
+        "lena.png",
+        output,
+        &["--sample", "480,20,4", "--adaptation", "cat02"],
+    );
+    println!("white-balance sample (CAT02) latency: {elapsed:?}");
+
+    assert!(
+        images_match(fixture, output, 0.0),
+        "white-balance sample (CAT02) output differs from fixture"
+    );
+    std::fs::remove_file(output).ok();
+}
+
+#[test]
+fn white_balance_black_reference() {
+    let input = "tests/fixtures/white-balance/black_actual.png";
+    let output = "tests/fixtures/white-balance/black_balanced_actual.png";
+    image::RgbImage::new(32, 32).save(input).unwrap();
+
+    // Nothing to neutralize: the image comes back unchanged instead of failing
+    run_white_balance(input, output, &["--auto", "gray-world"]);
+    assert!(images_match(input, output, 0.0), "a black image should pass through unchanged");
+    std::fs::remove_file(input).ok();
+    std::fs::remove_file(output).ok();
+}